@@ -0,0 +1,363 @@
+use reqwest::{
+    header::{HeaderMap, HeaderValue, ACCEPT_LANGUAGE},
+    Url,
+};
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    cache::{Cache, CacheLayer},
+    rate_limit::RateLimiter,
+    retry::RetryPolicy,
+    singleflight::Singleflight,
+    version::ApiVersion,
+    HolidayAPI, HolidayAPIError,
+};
+
+/// Which redirects the client follows. The key is part of the query string,
+/// so following a redirect to another host hands it over to that host.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RedirectPolicy {
+    /// Fail on any redirect.
+    None,
+    /// Follow up to 10 redirects within the same origin (scheme, host and
+    /// port), fail on any other.
+    #[default]
+    SameOrigin,
+    /// Follow up to 10 redirects to any host, as reqwest does by default.
+    /// Only use it behind a gateway you trust with the key.
+    Any,
+}
+
+impl RedirectPolicy {
+    const MAX_REDIRECTS: usize = 10;
+
+    pub(crate) fn to_reqwest(self) -> reqwest::redirect::Policy {
+        match self {
+            RedirectPolicy::None => reqwest::redirect::Policy::custom(|attempt| {
+                attempt.error("Redirects are disabled by the redirect policy")
+            }),
+            RedirectPolicy::SameOrigin => reqwest::redirect::Policy::custom(|attempt| {
+                let same_origin = attempt
+                    .previous()
+                    .first()
+                    .is_some_and(|first| first.origin() == attempt.url().origin());
+                if attempt.previous().len() >= Self::MAX_REDIRECTS {
+                    attempt.error("Too many redirects")
+                } else if same_origin {
+                    attempt.follow()
+                } else {
+                    attempt.error("Redirect to another origin refused by the redirect policy")
+                }
+            }),
+            RedirectPolicy::Any => reqwest::redirect::Policy::limited(Self::MAX_REDIRECTS),
+        }
+    }
+}
+
+/// Builds a `HolidayAPI` with custom client settings.
+///
+/// Created with `HolidayAPI::builder`.
+#[derive(Debug, Clone)]
+pub struct HolidayAPIBuilder {
+    key: String,
+    version: Result<ApiVersion, String>,
+    base_url: Option<Url>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    redirect: RedirectPolicy,
+    singleflight: bool,
+    cache: Option<CacheLayer>,
+    cache_max_age: Option<Duration>,
+    stale_while_revalidate: Option<Duration>,
+    retry: Option<RetryPolicy>,
+    accept_language: Option<String>,
+    strict_warnings: bool,
+    rate_limit: Option<u32>,
+}
+
+impl HolidayAPIBuilder {
+    pub(crate) fn new(key: &str) -> Self {
+        Self {
+            key: key.into(),
+            version: Ok(ApiVersion::default()),
+            base_url: None,
+            timeout: None,
+            connect_timeout: None,
+            redirect: RedirectPolicy::default(),
+            singleflight: false,
+            cache: None,
+            cache_max_age: None,
+            stale_while_revalidate: None,
+            retry: None,
+            accept_language: None,
+            strict_warnings: false,
+            rate_limit: None,
+        }
+    }
+
+    /// API version to use, e.g. `1`, `"v1"` or `ApiVersion::V1`. Defaults to
+    /// `ApiVersion::V1`.
+    ///
+    /// An invalid version is reported by `build`.
+    pub fn version<V>(&mut self, version: V) -> Self
+    where
+        V: TryInto<ApiVersion>,
+        V::Error: Into<HolidayAPIError>,
+    {
+        self.version = version.try_into().map_err(|err| err.into().to_string());
+        self.to_owned()
+    }
+
+    /// Send requests to `base_url` instead of `https://holidayapi.com/v<version>/`,
+    /// e.g. an internal gateway. See `HolidayAPI::with_base_url`.
+    pub fn base_url(&mut self, base_url: Url) -> Self {
+        self.base_url = Some(base_url);
+        self.to_owned()
+    }
+
+    /// Default time limit of every request, from sending it until its response
+    /// has been received. Unlimited by default.
+    ///
+    /// `Request::timeout` overrides it for a single request.
+    pub fn timeout(&mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self.to_owned()
+    }
+
+    /// Time limit for establishing a connection, e.g. to fail fast when the
+    /// host is unreachable while still allowing slow responses. Unlimited by
+    /// default, apart from the operating system's own limit.
+    ///
+    /// The connection is part of a request, so `timeout` still applies and
+    /// the shorter of the two wins.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// let api = HolidayAPI::builder("00000000-0000-0000-0000-000000000000")
+    ///     .connect_timeout(Duration::from_secs(2))
+    ///     .timeout(Duration::from_secs(30))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn connect_timeout(&mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self.to_owned()
+    }
+
+    /// Which redirects to follow. Defaults to `RedirectPolicy::SameOrigin`, so
+    /// that the key is never sent to another host.
+    pub fn redirect(&mut self, policy: RedirectPolicy) -> Self {
+        self.redirect = policy;
+        self.to_owned()
+    }
+
+    /// Share a single call between identical requests made concurrently, e.g.
+    /// the same `holidays` request from several tasks, instead of spending
+    /// quota on each of them. Disabled by default.
+    ///
+    /// Requests are identical when they target the same endpoint with the same
+    /// parameters. Only in-flight calls are shared, nothing is cached once a
+    /// call completes. The shared API is the built one and its clones.
+    ///
+    /// Applies to `Request::get_raw`, `Request::get_full`, `Request::get_as`
+    /// and the methods built on them, but not to `Request::stream` or
+    /// `HolidayAPI::custom_request`, which hand out the response itself. A
+    /// failed call's error is passed to one of the callers as is, and wrapped
+    /// in `HolidayAPIError::Shared` for the others.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let api = HolidayAPI::builder("00000000-0000-0000-0000-000000000000")
+    ///     .singleflight()
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn singleflight(&mut self) -> Self {
+        self.singleflight = true;
+        self.to_owned()
+    }
+
+    /// Serve successful responses from `cache` instead of sending the same
+    /// request again, e.g. a `MemoryCache` or your own `Cache` backed by
+    /// Redis. No cache by default.
+    ///
+    /// Applies to the same methods as `singleflight`. Pass an `Arc` to keep a
+    /// handle on the cache.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// let api = HolidayAPI::builder("00000000-0000-0000-0000-000000000000")
+    ///     .cache(MemoryCache::new())
+    ///     .cache_max_age(Duration::from_secs(24 * 60 * 60))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn cache(&mut self, cache: impl Cache + 'static) -> Self {
+        self.cache = Some(CacheLayer::new(Arc::new(cache), None));
+        self.to_owned()
+    }
+
+    /// Ignore cached responses older than `max_age`. Unlimited by default.
+    pub fn cache_max_age(&mut self, max_age: Duration) -> Self {
+        self.cache_max_age = Some(max_age);
+        self.to_owned()
+    }
+
+    /// Serve cached responses up to `window` older than `cache_max_age` right
+    /// away, and refresh them in the background, e.g. for the holidays of
+    /// the current year, which are requested often and rarely change.
+    /// Disabled by default, and without a `cache_max_age`.
+    ///
+    /// The tradeoff is consistency: a caller may get a response up to
+    /// `cache_max_age + window` old, and an update only shows up once the
+    /// refresh has completed, for the next caller. A key is refreshed once
+    /// at a time, and a failed refresh keeps the stale response until the
+    /// window is over, after which requests wait for a new response again.
+    /// Refreshes are spawned on the Tokio runtime and count against the
+    /// quota like any request.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// let api = HolidayAPI::builder("00000000-0000-0000-0000-000000000000")
+    ///     .cache(MemoryCache::new())
+    ///     .cache_max_age(Duration::from_secs(60 * 60))
+    ///     .cache_stale_while_revalidate(Duration::from_secs(24 * 60 * 60))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn cache_stale_while_revalidate(&mut self, window: Duration) -> Self {
+        self.stale_while_revalidate = Some(window);
+        self.to_owned()
+    }
+
+    /// Send failed requests again according to `policy`, e.g. on a `503
+    /// Service Unavailable`. Failed requests are not retried by default.
+    ///
+    /// Only sending a request and receiving its status is retried, not
+    /// reading the body. Retries count against your quota like any request.
+    pub fn retry(&mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self.to_owned()
+    }
+
+    /// `Accept-Language` header sent with every request, e.g. `"ja, en;q=0.5"`
+    /// for a gateway that keys on it. Not sent by default.
+    ///
+    /// The API ignores it: localize holidays with `language` instead. An
+    /// invalid header value is reported by `build`.
+    pub fn accept_language(&mut self, accept_language: &str) -> Self {
+        self.accept_language = Some(accept_language.into());
+        self.to_owned()
+    }
+
+    /// Treat a `warning` in a response as an error, e.g. to catch deprecated
+    /// parameters in CI. Disabled by default.
+    ///
+    /// Applies to `Request::get_full` and the methods built on it, which then
+    /// fail with `HolidayAPIError::Warning`. Raw bodies are returned as is.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let api = HolidayAPI::builder("00000000-0000-0000-0000-000000000000")
+    ///     .strict_warnings(cfg!(test))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn strict_warnings(&mut self, strict: bool) -> Self {
+        self.strict_warnings = strict;
+        self.to_owned()
+    }
+
+    /// Send at most `requests_per_second` requests, e.g. to spread out the
+    /// requests of a batch job. Unlimited by default.
+    ///
+    /// Requests are spaced evenly and wait their turn, retries included.
+    /// Clones of the API share the limit. A limit of 0 is reported by `build`.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let api = HolidayAPI::builder("00000000-0000-0000-0000-000000000000")
+    ///     .rate_limit(5)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn rate_limit(&mut self, requests_per_second: u32) -> Self {
+        self.rate_limit = Some(requests_per_second);
+        self.to_owned()
+    }
+
+    /// Construct the holiday API.
+    ///
+    /// # Errors
+    ///
+    /// Will return an `Err` if the key is not plausibly a valid one, the
+    /// version, the `Accept-Language` header or the rate limit is invalid, or
+    /// the HTTP client cannot be initialized.
+    pub fn build(&self) -> Result<HolidayAPI, HolidayAPIError> {
+        HolidayAPI::is_valid_key(&self.key)?;
+        let version = self
+            .version
+            .clone()
+            .map_err(HolidayAPIError::InvalidVersion)?;
+
+        let mut client = reqwest::Client::builder().redirect(self.redirect.to_reqwest());
+        if let Some(timeout) = self.timeout {
+            client = client.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            client = client.connect_timeout(timeout);
+        }
+        if let Some(accept_language) = &self.accept_language {
+            let value = HeaderValue::from_str(accept_language).map_err(|_| {
+                HolidayAPIError::InvalidRequest(format!(
+                    "Invalid Accept-Language header: {:?}",
+                    accept_language
+                ))
+            })?;
+            client = client.default_headers(HeaderMap::from_iter([(ACCEPT_LANGUAGE, value)]));
+        }
+        let rate_limit = match self.rate_limit {
+            Some(0) => {
+                return Err(HolidayAPIError::InvalidRequest(
+                    "Rate limit must be at least 1 request per second".into(),
+                ))
+            }
+            rate_limit => rate_limit.map(|per_second| Arc::new(RateLimiter::new(per_second))),
+        };
+        let client = client.build().map_err(|e| {
+            HolidayAPIError::RequestError(e, "Failed to build the HTTP client".into())
+        })?;
+
+        let mut api = HolidayAPI::construct_api(&self.key, version, client);
+        if let Some(base_url) = &self.base_url {
+            api.base_url = base_url.clone();
+        }
+        api.retry = self.retry.clone();
+        api.strict_warnings = self.strict_warnings;
+        api.rate_limit = rate_limit;
+        api.cache = self.cache.clone().map(|mut cache| {
+            cache.max_age = self.cache_max_age;
+            cache.stale_while_revalidate = self.stale_while_revalidate;
+            cache
+        });
+        if self.singleflight {
+            api.singleflight = Some(Arc::new(Singleflight::default()));
+        }
+        Ok(api)
+    }
+}
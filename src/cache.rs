@@ -0,0 +1,238 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+/// A response body stored in a `Cache`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResponse {
+    pub body: String,
+    /// When the response was received.
+    pub stored_at: SystemTime,
+}
+
+impl CachedResponse {
+    /// How long ago the response was received, zero if `stored_at` is in
+    /// the future.
+    pub fn age(&self) -> Duration {
+        self.stored_at.elapsed().unwrap_or_default()
+    }
+}
+
+/// Storage for successful response bodies, consulted before sending a
+/// request. See `HolidayAPIBuilder::cache`.
+///
+/// Keys identify a request by its url, the API key excluded: base url,
+/// version, endpoint and parameters. Both methods are called from async
+/// code, so an implementation backed by the network or the filesystem
+/// should keep them quick, e.g. by serving from memory and writing in the
+/// background.
+///
+/// # Examples
+///
+/// A cache that never stores anything:
+///
+/// ```
+/// use holidayapi_rust::prelude::*;
+///
+/// struct NoCache;
+///
+/// impl Cache for NoCache {
+///     fn get(&self, _key: &str) -> Option<CachedResponse> {
+///         None
+///     }
+///
+///     fn set(&self, _key: &str, _response: CachedResponse) {}
+/// }
+///
+/// let api = HolidayAPI::builder("00000000-0000-0000-0000-000000000000")
+///     .cache(NoCache)
+///     .build()
+///     .unwrap();
+/// ```
+pub trait Cache: Send + Sync {
+    /// Returns the response stored for `key`, if any.
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    /// Stores `response` for `key`, replacing any previous one.
+    fn set(&self, key: &str, response: CachedResponse);
+}
+
+/// An unbounded in-memory `Cache`, shared by the clones of an API.
+///
+/// Entries are only dropped when replaced, bound their lifetime with
+/// `HolidayAPIBuilder::cache_max_age`.
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    entries: Mutex<HashMap<String, CachedResponse>>,
+}
+
+impl MemoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of stored responses.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Cache for MemoryCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.get(key).cloned()
+    }
+
+    fn set(&self, key: &str, response: CachedResponse) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert(key.to_owned(), response);
+    }
+}
+
+impl<C: Cache + ?Sized> Cache for Arc<C> {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        (**self).get(key)
+    }
+
+    fn set(&self, key: &str, response: CachedResponse) {
+        (**self).set(key, response)
+    }
+}
+
+/// The cache of an API along with its settings.
+#[derive(Clone)]
+pub(crate) struct CacheLayer {
+    cache: Arc<dyn Cache>,
+    pub(crate) max_age: Option<Duration>,
+    pub(crate) stale_while_revalidate: Option<Duration>,
+    /// Keys being refreshed in the background, to refresh each only once.
+    refreshing: Arc<Mutex<HashSet<String>>>,
+}
+
+impl fmt::Debug for CacheLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CacheLayer")
+            .field("max_age", &self.max_age)
+            .field("stale_while_revalidate", &self.stale_while_revalidate)
+            .finish_non_exhaustive()
+    }
+}
+
+/// What the cache holds for a key, see `CacheLayer::lookup`.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Lookup {
+    /// Not older than `max_age`, served as is.
+    Fresh(String),
+    /// Older than `max_age`, but within the `stale_while_revalidate` window:
+    /// served while it is refreshed in the background.
+    Stale(String),
+    /// Nothing usable, the request has to be sent.
+    Miss,
+}
+
+impl CacheLayer {
+    pub(crate) fn new(cache: Arc<dyn Cache>, max_age: Option<Duration>) -> Self {
+        Self {
+            cache,
+            max_age,
+            stale_while_revalidate: None,
+            refreshing: Arc::default(),
+        }
+    }
+
+    /// Returns the body stored for `key`, telling whether it is older than
+    /// `max_age`.
+    pub(crate) fn lookup(&self, key: &str) -> Lookup {
+        let Some(cached) = self.cache.get(key) else {
+            return Lookup::Miss;
+        };
+        let Some(max_age) = self.max_age else {
+            return Lookup::Fresh(cached.body);
+        };
+        let age = cached.age();
+        match self.stale_while_revalidate {
+            _ if age <= max_age => Lookup::Fresh(cached.body),
+            Some(window) if age <= max_age.saturating_add(window) => Lookup::Stale(cached.body),
+            _ => Lookup::Miss,
+        }
+    }
+
+    pub(crate) fn set(&self, key: &str, body: &str) {
+        self.cache.set(
+            key,
+            CachedResponse {
+                body: body.to_owned(),
+                stored_at: SystemTime::now(),
+            },
+        );
+    }
+
+    /// Marks `key` as being refreshed, `false` if it already is.
+    pub(crate) fn start_refresh(&self, key: &str) -> bool {
+        let mut refreshing = self.refreshing.lock().unwrap_or_else(|e| e.into_inner());
+        refreshing.insert(key.to_owned())
+    }
+
+    pub(crate) fn finish_refresh(&self, key: &str) {
+        let mut refreshing = self.refreshing.lock().unwrap_or_else(|e| e.into_inner());
+        refreshing.remove(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_age() {
+        let cache = Arc::new(MemoryCache::new());
+        let layer = CacheLayer::new(cache.clone(), Some(Duration::from_secs(60)));
+        assert_eq!(layer.lookup("countries"), Lookup::Miss);
+
+        layer.set("countries", "fresh");
+        assert_eq!(layer.lookup("countries"), Lookup::Fresh("fresh".into()));
+
+        cache.set(
+            "countries",
+            CachedResponse {
+                body: "stale".into(),
+                stored_at: SystemTime::now() - Duration::from_secs(120),
+            },
+        );
+        assert_eq!(layer.lookup("countries"), Lookup::Miss);
+        assert_eq!(cache.len(), 1);
+
+        let unbounded = CacheLayer::new(cache, None);
+        assert_eq!(unbounded.lookup("countries"), Lookup::Fresh("stale".into()));
+    }
+
+    #[test]
+    fn test_stale_while_revalidate() {
+        let cache = Arc::new(MemoryCache::new());
+        let mut layer = CacheLayer::new(cache.clone(), Some(Duration::from_secs(60)));
+        layer.stale_while_revalidate = Some(Duration::from_secs(60));
+        let stored = |age| CachedResponse {
+            body: "cached".into(),
+            stored_at: SystemTime::now() - Duration::from_secs(age),
+        };
+
+        cache.set("countries", stored(30));
+        assert_eq!(layer.lookup("countries"), Lookup::Fresh("cached".into()));
+        cache.set("countries", stored(90));
+        assert_eq!(layer.lookup("countries"), Lookup::Stale("cached".into()));
+        cache.set("countries", stored(150));
+        assert_eq!(layer.lookup("countries"), Lookup::Miss);
+
+        assert!(layer.start_refresh("countries"));
+        assert!(!layer.clone().start_refresh("countries"));
+        layer.finish_refresh("countries");
+        assert!(layer.start_refresh("countries"));
+    }
+}
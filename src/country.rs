@@ -0,0 +1,97 @@
+use std::{fmt, str::FromStr};
+
+use crate::HolidayAPIError;
+
+/// A country code checked against the ISO 3166-1 alpha-2 codes, which the
+/// API's countries are based on, e.g. for the `country` of a holidays request.
+///
+/// Parsing is case-insensitive, the code is kept in uppercase. Subdivision
+/// codes such as `US-CA` are not country codes and fail to parse.
+///
+/// # Examples
+/// ```
+/// use holidayapi_rust::prelude::*;
+///
+/// let japan: CountryCode = "jp".parse().unwrap();
+/// assert_eq!(japan.as_str(), "JP");
+///
+/// assert!(CountryCode::try_from("UK").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CountryCode(&'static str);
+
+impl CountryCode {
+    /// Every known code, in alphabetical order.
+    pub const ALL: &'static [&'static str] = &[
+        "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX",
+        "AZ", "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ",
+        "BR", "BS", "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK",
+        "CL", "CM", "CN", "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM",
+        "DO", "DZ", "EC", "EE", "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK", "FM", "FO", "FR",
+        "GA", "GB", "GD", "GE", "GF", "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS",
+        "GT", "GU", "GW", "GY", "HK", "HM", "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN",
+        "IO", "IQ", "IR", "IS", "IT", "JE", "JM", "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN",
+        "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC", "LI", "LK", "LR", "LS", "LT", "LU", "LV",
+        "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK", "ML", "MM", "MN", "MO", "MP", "MQ",
+        "MR", "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA", "NC", "NE", "NF", "NG", "NI",
+        "NL", "NO", "NP", "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG", "PH", "PK", "PL", "PM",
+        "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW", "SA", "SB", "SC",
+        "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS", "ST", "SV",
+        "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO", "TR",
+        "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI",
+        "VN", "VU", "WF", "WS", "YE", "YT", "ZA", "ZM", "ZW",
+    ];
+
+    /// The code in uppercase, e.g. `JP`.
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl AsRef<str> for CountryCode {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl fmt::Display for CountryCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl FromStr for CountryCode {
+    type Err = HolidayAPIError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .binary_search(&code.to_ascii_uppercase().as_str())
+            .map(|index| CountryCode(Self::ALL[index]))
+            .map_err(|_| HolidayAPIError::InvalidCountry(code.to_owned()))
+    }
+}
+
+impl TryFrom<&str> for CountryCode {
+    type Error = HolidayAPIError;
+
+    fn try_from(code: &str) -> Result<Self, Self::Error> {
+        code.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_country() {
+        assert!(CountryCode::ALL.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(CountryCode::try_from("jP").unwrap().as_str(), "JP");
+        for code in ["UK", "", "USA", "US-CA"] {
+            assert!(matches!(
+                CountryCode::try_from(code),
+                Err(HolidayAPIError::InvalidCountry(invalid)) if invalid == code
+            ));
+        }
+    }
+}
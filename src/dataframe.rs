@@ -0,0 +1,85 @@
+use chrono::NaiveDate;
+use polars::prelude::*;
+
+use crate::responses::Holiday;
+
+/// Converts `holidays` into a DataFrame with one row per holiday and the
+/// columns `name`, `date`, `observed`, `public` and `country`.
+///
+/// `date` and `observed` are `Date` columns, null where the API's date does
+/// not parse.
+///
+/// # Examples
+/// ```
+/// use holidayapi_rust::prelude::*;
+/// use polars::prelude::*;
+///
+/// async fn public_count(api: HolidayAPI) -> Result<usize, Box<dyn std::error::Error>> {
+///     let holidays = api.holidays("US", 2020).get().await?;
+///     let df = holidays_to_dataframe(&holidays)?;
+///     Ok(df.column("public")?.bool()?.sum().unwrap_or_default() as usize)
+/// }
+/// ```
+pub fn holidays_to_dataframe(holidays: &[Holiday]) -> PolarsResult<DataFrame> {
+    let strings = |name: &str, field: fn(&Holiday) -> &str| {
+        let values: Vec<&str> = holidays.iter().map(field).collect();
+        Column::new(name.into(), values)
+    };
+    let dates = |name: &str, field: fn(&Holiday) -> &str| {
+        let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).expect("Epoch is a valid date");
+        let days: Vec<Option<i32>> = holidays
+            .iter()
+            .map(|holiday| {
+                let date = NaiveDate::parse_from_str(field(holiday), "%Y-%m-%d").ok()?;
+                i32::try_from((date - epoch).num_days()).ok()
+            })
+            .collect();
+        Column::new(name.into(), days).cast(&DataType::Date)
+    };
+    let public: Vec<bool> = holidays.iter().map(|holiday| holiday.public).collect();
+
+    DataFrame::new(vec![
+        strings("name", |holiday| &holiday.name),
+        dates("date", |holiday| &holiday.date)?,
+        dates("observed", |holiday| &holiday.observed)?,
+        Column::new("public".into(), public),
+        strings("country", |holiday| &holiday.country),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_holidays_to_dataframe() {
+        let holidays: Vec<Holiday> = ["2020-07-04", "not a date"]
+            .into_iter()
+            .map(|date| {
+                serde_json::from_value(serde_json::json!({
+                    "name": "Independence Day",
+                    "date": date,
+                    "observed": "2020-07-03",
+                    "public": true,
+                    "country": "US",
+                    "uuid": "88268759-9b90-468c-804f-b729b8418e7c",
+                    "weekday": {
+                        "date": { "name": "Saturday", "numeric": "6" },
+                        "observed": { "name": "Friday", "numeric": "5" }
+                    }
+                }))
+                .unwrap()
+            })
+            .collect();
+
+        let df = holidays_to_dataframe(&holidays).unwrap();
+        assert_eq!(df.shape(), (2, 5));
+        assert_eq!(df.column("date").unwrap().dtype(), &DataType::Date);
+        assert_eq!(df.column("date").unwrap().null_count(), 1);
+
+        let observed = df.column("observed").unwrap().date().unwrap();
+        let expected = NaiveDate::from_ymd_opt(2020, 7, 3).unwrap()
+            - NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+        assert_eq!(observed.physical().get(0), Some(expected.num_days() as i32));
+    }
+}
@@ -0,0 +1,798 @@
+#[cfg(feature = "chrono")]
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use futures_util::{future, stream, StreamExt, TryStreamExt};
+#[cfg(feature = "chrono")]
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::{
+    requests::Request,
+    responses::{Country, Holiday, HolidaysResponse, Language},
+    utils::flatten_subdivisions,
+    validation::ValidationError,
+    HolidayAPI, HolidayAPIError,
+};
+
+/// Convenience methods built on top of the raw endpoints.
+impl HolidayAPI {
+    /// Fetches every country and returns all their subdivisions as a flat list
+    /// of `(country_code, subdivision_code, subdivision_name)` tuples.
+    ///
+    /// The countries list is large and rarely changes, consider keeping the
+    /// result around instead of calling this repeatedly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn index(api: HolidayAPI) -> Result<(), HolidayAPIError> {
+    ///     for (country, code, name) in api.all_subdivisions().await? {
+    ///         println!("{country} {code} {name}");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn all_subdivisions(&self) -> Result<Vec<(String, String, String)>, HolidayAPIError> {
+        let countries = self.countries().get().await?;
+        Ok(flatten_subdivisions(&countries))
+    }
+
+    /// Fetches every country once and keeps them in this API, for lookups
+    /// with `country_by_code` that never touch the network.
+    ///
+    /// The list is not refreshed on its own, call this again to replace it.
+    /// Clones made afterwards share the list, clones made before do not see it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn country_name(mut api: HolidayAPI) -> Result<Option<String>, HolidayAPIError> {
+    ///     api.preload_countries().await?;
+    ///     Ok(api.country_by_code("jp").map(|country| country.name.clone()))
+    /// }
+    /// ```
+    pub async fn preload_countries(&mut self) -> Result<(), HolidayAPIError> {
+        let countries = self.countries().get().await?;
+        self.countries = Some(Arc::new(countries));
+        Ok(())
+    }
+
+    /// Looks up a country by its code, case insensitively, in the list kept by
+    /// `preload_countries`. Always `None` before it has been called.
+    pub fn country_by_code(&self, code: &str) -> Option<&Country> {
+        self.countries
+            .as_ref()?
+            .iter()
+            .find(|country| country.code.eq_ignore_ascii_case(code))
+    }
+
+    /// Like `preload_countries`, but fetches the languages as well, both at
+    /// once, for lookups with `country_by_code` and `language_by_code`.
+    ///
+    /// Neither list is kept unless both requests succeed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn init(mut api: HolidayAPI) -> Result<HolidayAPI, HolidayAPIError> {
+    ///     api.preload_reference_data().await?;
+    ///     assert!(api.language_by_code("en").is_some());
+    ///     Ok(api)
+    /// }
+    /// ```
+    pub async fn preload_reference_data(&mut self) -> Result<(), HolidayAPIError> {
+        let (countries, languages) =
+            future::try_join(self.countries().get(), self.languages().get()).await?;
+        self.countries = Some(Arc::new(countries));
+        self.languages = Some(Arc::new(languages));
+        Ok(())
+    }
+
+    /// Looks up a language by its code, case insensitively, in the list kept
+    /// by `preload_reference_data`. Always `None` before it has been called.
+    pub fn language_by_code(&self, code: &str) -> Option<&Language> {
+        self.languages
+            .as_ref()?
+            .iter()
+            .find(|language| language.code.eq_ignore_ascii_case(code))
+    }
+
+    /// Returns the holidays of `country` on the given date, if any. A date may
+    /// have several holidays, e.g. a national and a religious one.
+    ///
+    /// A shorthand for `api.holidays(country, year).month(month).day(day)`,
+    /// keeping only the holidays whose `date` is exactly that day.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn christmas(api: HolidayAPI) -> Result<bool, HolidayAPIError> {
+    ///     Ok(!api.holiday_on("US", 2020, 12, 25).await?.is_empty())
+    /// }
+    /// ```
+    pub async fn holiday_on(
+        &self,
+        country: &str,
+        year: i32,
+        month: i32,
+        day: i32,
+    ) -> Result<Vec<Holiday>, HolidayAPIError> {
+        let date = format!("{:04}-{:02}-{:02}", year, month, day);
+        let holidays = self
+            .holidays(country, year)
+            .month(month)
+            .day(day)
+            .get()
+            .await?;
+        Ok(holidays
+            .into_iter()
+            .filter(|holiday| holiday.date == date)
+            .collect())
+    }
+
+    /// Whether `country` has a holiday, public or not, whose actual `date` is
+    /// the given day, see `holiday_on`.
+    ///
+    /// A holiday on a Sunday observed on the Monday is a holiday on the
+    /// Sunday here, not on the Monday. Use `is_holiday_observed` for the days
+    /// off, e.g. for payroll.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn independence_day(api: HolidayAPI) -> Result<bool, HolidayAPIError> {
+    ///     api.is_holiday("US", 2020, 7, 4).await
+    /// }
+    /// ```
+    pub async fn is_holiday(
+        &self,
+        country: &str,
+        year: i32,
+        month: i32,
+        day: i32,
+    ) -> Result<bool, HolidayAPIError> {
+        Ok(!self.holiday_on(country, year, month, day).await?.is_empty())
+    }
+
+    /// Whether `country` observes a holiday, public or not, on the given day,
+    /// i.e. whether its `observed` date is that day, whatever its actual date.
+    ///
+    /// A holiday on a Sunday observed on the Monday is a holiday on the
+    /// Monday here, not on the Sunday. Fetches the whole year, as well as the
+    /// previous or next one in January and December, since a holiday can be
+    /// observed in another year than its date, e.g. a New Year's Day on a
+    /// Saturday observed on December 31st.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn day_off(api: HolidayAPI) -> Result<bool, HolidayAPIError> {
+    ///     // Independence Day 2020 is on a Saturday, observed on Friday.
+    ///     api.is_holiday_observed("US", 2020, 7, 3).await
+    /// }
+    /// ```
+    pub async fn is_holiday_observed(
+        &self,
+        country: &str,
+        year: i32,
+        month: i32,
+        day: i32,
+    ) -> Result<bool, HolidayAPIError> {
+        let date = format!("{:04}-{:02}-{:02}", year, month, day);
+        let years = match month {
+            1 => vec![year - 1, year],
+            12 => vec![year, year + 1],
+            _ => vec![year],
+        };
+        let holidays = future::try_join_all(
+            years
+                .into_iter()
+                .map(|year| self.holidays(country, year).get()),
+        )
+        .await?;
+        Ok(holidays
+            .iter()
+            .flatten()
+            .any(|holiday| holiday.observed == date))
+    }
+
+    /// Returns the first holiday of `country` in `year` matching `query`, e.g.
+    /// to look up a holiday by part of its name, or `None` without a match.
+    ///
+    /// A shorthand for `api.holidays(country, year).search(query)`, keeping
+    /// the first result. The API requires at least 5 characters to search,
+    /// a shorter `query` fails with `ValidationError::TooShort` without
+    /// sending the request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn thanksgiving(api: HolidayAPI) -> Result<Option<String>, HolidayAPIError> {
+    ///     let holiday = api.search_first("US", 2020, "thanksgiving").await?;
+    ///     Ok(holiday.map(|holiday| holiday.date))
+    /// }
+    /// ```
+    pub async fn search_first(
+        &self,
+        country: &str,
+        year: i32,
+        query: &str,
+    ) -> Result<Option<Holiday>, HolidayAPIError> {
+        let minimum = Request::<HolidaysResponse>::SEARCH_MIN_CHARS;
+        if query.trim().chars().count() < minimum {
+            return Err(ValidationError::TooShort {
+                parameter: "search",
+                minimum,
+            }
+            .into());
+        }
+        let holidays = self.holidays(country, year).search(query).get().await?;
+        Ok(holidays.into_iter().next())
+    }
+
+    /// Returns the holidays on the given date of each of `countries`, e.g. to
+    /// show what is celebrated somewhere today, with up to `max_concurrent`
+    /// requests at a time (at least one).
+    ///
+    /// Every country gets its own result, see `holiday_on`, so a failure for
+    /// one of them does not hide the others. Countries without a holiday
+    /// that day get an empty list.
+    ///
+    /// Results are in the order of `countries`, whatever order the requests
+    /// complete in, so the output is the same from one run to the next.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn celebrated(api: HolidayAPI) {
+    ///     let countries = ["US", "JP", "FR", "BR"];
+    ///     for (country, holidays) in api.holidays_on_date(&countries, 2020, 12, 25, 2).await {
+    ///         match holidays {
+    ///             Ok(holidays) => println!("{}: {} holidays", country, holidays.len()),
+    ///             Err(err) => println!("{}: {}", country, err),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub async fn holidays_on_date(
+        &self,
+        countries: &[&str],
+        year: i32,
+        month: i32,
+        day: i32,
+        max_concurrent: usize,
+    ) -> Vec<(String, Result<Vec<Holiday>, HolidayAPIError>)> {
+        stream::iter(countries)
+            .map(|country| async move {
+                let holidays = self.holiday_on(country, year, month, day).await;
+                (country.to_string(), holidays)
+            })
+            .buffered(max_concurrent.max(1))
+            .collect()
+            .await
+    }
+
+    /// Requests the US public holidays of `year`, a shorthand for
+    /// `api.holidays("US", year).public(true)`.
+    ///
+    /// "Federal" here means what the API reports as public holidays for the
+    /// US, it is not checked against the official list of federal holidays.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn federal(api: HolidayAPI) -> Result<Vec<Holiday>, HolidayAPIError> {
+    ///     api.us_federal_holidays(2020).get().await
+    /// }
+    /// ```
+    pub fn us_federal_holidays(&self, year: i32) -> Request<HolidaysResponse> {
+        self.holidays("US", year).public(true)
+    }
+
+    /// Returns the number of public holidays of `country` in `year`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn days_off(api: HolidayAPI) -> Result<usize, HolidayAPIError> {
+    ///     api.public_holiday_count("JP", 2020).await
+    /// }
+    /// ```
+    pub async fn public_holiday_count(
+        &self,
+        country: &str,
+        year: i32,
+    ) -> Result<usize, HolidayAPIError> {
+        let holidays = self.holidays(country, year).public(true).get().await?;
+        Ok(holidays.iter().filter(|holiday| holiday.public).count())
+    }
+
+    /// Returns the number of public holidays in `year` of each of `countries`,
+    /// e.g. to compare countries, with up to `max_concurrent` requests at a
+    /// time (at least one).
+    ///
+    /// Counts are in the order of `countries`, whatever order the requests
+    /// complete in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn most(api: HolidayAPI) -> Result<Option<String>, HolidayAPIError> {
+    ///     let counts = api.public_holiday_counts(&["US", "JP", "FR"], 2020, 2).await?;
+    ///     Ok(counts.into_iter().max_by_key(|(_, count)| *count).map(|(country, _)| country))
+    /// }
+    /// ```
+    pub async fn public_holiday_counts(
+        &self,
+        countries: &[&str],
+        year: i32,
+        max_concurrent: usize,
+    ) -> Result<Vec<(String, usize)>, HolidayAPIError> {
+        stream::iter(countries)
+            .map(|country| async move {
+                let count = self.public_holiday_count(country, year).await?;
+                Ok((country.to_string(), count))
+            })
+            .buffered(max_concurrent.max(1))
+            .try_collect()
+            .await
+    }
+
+    /// Returns the next `n` holidays of `country` after today, sorted by date.
+    ///
+    /// Fetches the holidays of next year too when this year has fewer than
+    /// `n` holidays left. Today's holidays are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn upcoming(api: HolidayAPI) -> Result<(), HolidayAPIError> {
+    ///     for holiday in api.next_holidays("us", 3).await? {
+    ///         println!("{} {}", holiday.date, holiday.name);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub async fn next_holidays(
+        &self,
+        country: &str,
+        n: usize,
+    ) -> Result<Vec<Holiday>, HolidayAPIError> {
+        let today = chrono::Local::now().date_naive();
+        self.next_holidays_after(country, today, n).await
+    }
+
+    /// Returns the number of days from today to the next holiday of
+    /// `country`, e.g. for a countdown. Today's holidays are not included.
+    ///
+    /// Looks into next year when this year has no holiday left, and returns
+    /// `None` when next year has none either.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn countdown(api: HolidayAPI) -> Result<(), HolidayAPIError> {
+    ///     if let Some(days) = api.days_until_next_holiday("us").await? {
+    ///         println!("{days} days to go");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub async fn days_until_next_holiday(
+        &self,
+        country: &str,
+    ) -> Result<Option<i64>, HolidayAPIError> {
+        let today = chrono::Local::now().date_naive();
+        self.days_until_next_holiday_after(country, today).await
+    }
+
+    #[cfg(feature = "chrono")]
+    async fn days_until_next_holiday_after(
+        &self,
+        country: &str,
+        date: NaiveDate,
+    ) -> Result<Option<i64>, HolidayAPIError> {
+        let next = self.next_holidays_after(country, date, 1).await?;
+        let Some(holiday) = next.first() else {
+            return Ok(None);
+        };
+        let next_date = NaiveDate::parse_from_str(&holiday.date, "%Y-%m-%d").map_err(|_| {
+            HolidayAPIError::InvalidResponse(format!("Invalid holiday date: {}", holiday.date))
+        })?;
+        Ok(Some((next_date - date).num_days()))
+    }
+
+    #[cfg(feature = "chrono")]
+    async fn next_holidays_after(
+        &self,
+        country: &str,
+        date: NaiveDate,
+        n: usize,
+    ) -> Result<Vec<Holiday>, HolidayAPIError> {
+        let after = date.format("%Y-%m-%d").to_string();
+        let mut next = Vec::new();
+
+        for year in [date.year(), date.year() + 1] {
+            if next.len() >= n {
+                break;
+            }
+            let mut holidays: Vec<_> = self
+                .holidays(country, year)
+                .get()
+                .await?
+                .into_iter()
+                .filter(|holiday| holiday.date > after)
+                .collect();
+            holidays.sort_by(|a, b| a.date.cmp(&b.date));
+            next.extend(holidays);
+        }
+
+        next.truncate(n);
+        Ok(next)
+    }
+
+    /// Counts the workdays of `country` from `start` to `end`, both included,
+    /// with `weekend` as the days off of every week, e.g. Friday and Saturday.
+    ///
+    /// The `workdays` endpoint always treats Saturday and Sunday as the
+    /// weekend and has no parameter to change it, so this computes the count
+    /// locally instead: every day that is neither in `weekend` nor the
+    /// observed date of a public holiday is a workday. The public holidays of
+    /// every year in the range are fetched, one request per year, as well as
+    /// those of the neighbouring year when the range reaches January or
+    /// December.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{NaiveDate, Weekday};
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn july(api: HolidayAPI) -> Result<u32, HolidayAPIError> {
+    ///     let start = NaiveDate::from_ymd_opt(2020, 7, 1).unwrap();
+    ///     let end = NaiveDate::from_ymd_opt(2020, 7, 31).unwrap();
+    ///     api.workdays_with_weekend("AE", start, end, &[Weekday::Fri, Weekday::Sat])
+    ///         .await
+    /// }
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub async fn workdays_with_weekend(
+        &self,
+        country: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+        weekend: &[Weekday],
+    ) -> Result<u32, HolidayAPIError> {
+        let (start, end) = (start.min(end), start.max(end));
+        let mut days_off = DaysOff::new(self, country, weekend);
+        let mut workdays = 0;
+        for date in start.iter_days().take_while(|date| *date <= end) {
+            if !days_off.contains(date).await? {
+                workdays += 1;
+            }
+        }
+        Ok(workdays)
+    }
+
+    /// Returns the date `days` workdays after `start`, or before it when
+    /// `days` is negative, with `weekend` as the days off of every week.
+    ///
+    /// Like `workdays_with_weekend`, this is computed locally from the public
+    /// holidays of `country`, as the `workday` endpoint has no parameter to
+    /// change the weekend. `start` itself is never counted.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `HolidayAPIError::InvalidRequest` if `weekend` contains all
+    /// seven days of the week, which leaves no workday.
+    #[cfg(feature = "chrono")]
+    pub async fn workday_with_weekend(
+        &self,
+        country: &str,
+        start: NaiveDate,
+        days: i32,
+        weekend: &[Weekday],
+    ) -> Result<NaiveDate, HolidayAPIError> {
+        let mut days_off = DaysOff::new(self, country, weekend);
+        if days_off.weekend.len() >= 7 {
+            return Err(HolidayAPIError::InvalidRequest(
+                "A weekend of seven days leaves no workday".into(),
+            ));
+        }
+
+        let step = |date: NaiveDate| match days.is_negative() {
+            true => date.checked_sub_days(Days::new(1)),
+            false => date.checked_add_days(Days::new(1)),
+        };
+        let mut date = start;
+        let mut remaining = days.unsigned_abs();
+        while remaining > 0 {
+            date = step(date).ok_or_else(|| {
+                HolidayAPIError::InvalidResponse(format!("No workday {} days from {}", days, start))
+            })?;
+            if !days_off.contains(date).await? {
+                remaining -= 1;
+            }
+        }
+        Ok(date)
+    }
+}
+
+/// Weekend days and observed public holidays of a country, fetched a year
+/// at a time.
+#[cfg(feature = "chrono")]
+struct DaysOff<'a> {
+    api: &'a HolidayAPI,
+    country: &'a str,
+    weekend: HashSet<Weekday>,
+    years: HashSet<i32>,
+    observed: HashSet<NaiveDate>,
+}
+
+#[cfg(feature = "chrono")]
+impl<'a> DaysOff<'a> {
+    fn new(api: &'a HolidayAPI, country: &'a str, weekend: &[Weekday]) -> Self {
+        Self {
+            api,
+            country,
+            weekend: weekend.iter().copied().collect(),
+            years: HashSet::new(),
+            observed: HashSet::new(),
+        }
+    }
+
+    async fn contains(&mut self, date: NaiveDate) -> Result<bool, HolidayAPIError> {
+        if self.weekend.contains(&date.weekday()) {
+            return Ok(true);
+        }
+        // A holiday can be observed in another year than its date, see
+        // `is_holiday_observed`.
+        let years = match date.month() {
+            1 => vec![date.year() - 1, date.year()],
+            12 => vec![date.year(), date.year() + 1],
+            _ => vec![date.year()],
+        };
+        for year in years {
+            if !self.years.insert(year) {
+                continue;
+            }
+            let holidays = self
+                .api
+                .holidays(self.country, year)
+                .public(true)
+                .get()
+                .await?;
+            self.observed.extend(holidays.iter().filter_map(|holiday| {
+                NaiveDate::parse_from_str(&holiday.observed, "%Y-%m-%d").ok()
+            }));
+        }
+        Ok(self.observed.contains(&date))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixtures() -> HolidayAPI {
+        HolidayAPI::with_fixtures(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures")).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_all_subdivisions() {
+        let subdivisions = fixtures().all_subdivisions().await.unwrap();
+        assert_eq!(
+            subdivisions[1],
+            ("US".into(), "US-CA".into(), "California".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_preload_countries() {
+        let mut api = fixtures();
+        assert!(api.country_by_code("US").is_none());
+
+        api.preload_countries().await.unwrap();
+        assert_eq!(api.country_by_code("us").unwrap().name, "United States");
+        assert_eq!(api.clone().country_by_code("JP").unwrap().code, "JP");
+        assert!(api.country_by_code("XX").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_preload_reference_data() {
+        let mut api = fixtures();
+        assert!(api.language_by_code("en").is_none());
+
+        api.preload_reference_data().await.unwrap();
+        assert_eq!(api.language_by_code("JA").unwrap().name, "Japanese");
+        assert_eq!(api.country_by_code("us").unwrap().name, "United States");
+        assert!(api.language_by_code("xx").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_holiday_on() {
+        let api = fixtures();
+        let holidays = api.holiday_on("US", 2020, 7, 4).await.unwrap();
+        assert_eq!(holidays.len(), 1);
+        assert_eq!(holidays[0].name, "Independence Day");
+        assert!(api.holiday_on("US", 2020, 7, 5).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_is_holiday() {
+        let api = fixtures();
+        // Independence Day 2020 is on Saturday the 4th, observed on the 3rd.
+        assert!(api.is_holiday("US", 2020, 7, 4).await.unwrap());
+        assert!(!api.is_holiday("US", 2020, 7, 3).await.unwrap());
+        assert!(api.is_holiday_observed("US", 2020, 7, 3).await.unwrap());
+        assert!(!api.is_holiday_observed("US", 2020, 7, 4).await.unwrap());
+        assert!(api.is_holiday_observed("US", 2020, 12, 25).await.unwrap());
+        assert!(api.is_holiday_observed("US", 2020, 1, 1).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_search_first() {
+        let api = fixtures();
+        // The fixture ignores the search, the first holiday is returned.
+        let holiday = api.search_first("US", 2020, "New Year").await.unwrap();
+        assert_eq!(holiday.unwrap().name, "New Year's Day");
+        assert!(matches!(
+            api.search_first("US", 2020, " Day ").await,
+            Err(HolidayAPIError::Validation(ValidationError::TooShort {
+                parameter: "search",
+                minimum: 5
+            }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_public_holiday_counts() {
+        let api = fixtures();
+        // The fixture ignores the public filter, Halloween is not counted.
+        assert_eq!(api.public_holiday_count("US", 2020).await.unwrap(), 3);
+        let counts = api
+            .public_holiday_counts(&["US", "JP"], 2020, 1)
+            .await
+            .unwrap();
+        assert_eq!(counts, [("US".into(), 3), ("JP".into(), 3)]);
+    }
+
+    #[tokio::test]
+    async fn test_holidays_on_date() {
+        let api = fixtures();
+        let holidays = api
+            .holidays_on_date(&["US", "JP", "FR"], 2020, 12, 25, 2)
+            .await;
+        let countries: Vec<_> = holidays
+            .iter()
+            .map(|(country, _)| country.as_str())
+            .collect();
+        assert_eq!(countries, ["US", "JP", "FR"]);
+        assert_eq!(holidays[0].1.as_ref().unwrap()[0].name, "Christmas Day");
+
+        let none = api.holidays_on_date(&["US"], 2020, 12, 26, 0).await;
+        assert!(none[0].1.as_ref().unwrap().is_empty());
+        let invalid = api.holidays_on_date(&["US"], 2020, 12, 32, 1).await;
+        assert!(invalid[0].1.is_err());
+    }
+
+    #[test]
+    fn test_us_federal_holidays() {
+        let spec = fixtures().us_federal_holidays(2020).to_spec();
+        assert_eq!(spec.endpoint, "holidays");
+        assert_eq!(spec.parameters["country"], "US");
+        assert_eq!(spec.parameters["year"], "2020");
+        assert_eq!(spec.parameters["public"], "true");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[tokio::test]
+    async fn test_next_holidays() {
+        let api = fixtures();
+        let names = |holidays: Vec<Holiday>| -> Vec<String> {
+            holidays.into_iter().map(|h| h.name).collect()
+        };
+
+        let july = NaiveDate::from_ymd_opt(2020, 7, 1).unwrap();
+        let next = api.next_holidays_after("us", july, 2).await.unwrap();
+        assert_eq!(names(next), ["Independence Day", "Halloween"]);
+
+        // Fixtures ignore the year, so "2021" serves the 2020 holidays again.
+        let december = NaiveDate::from_ymd_opt(2020, 12, 1).unwrap();
+        let next = api.next_holidays_after("us", december, 2).await.unwrap();
+        assert_eq!(names(next), ["Christmas Day", "Christmas Day"]);
+
+        assert!(api
+            .next_holidays_after("us", july, 0)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[tokio::test]
+    async fn test_days_until_next_holiday() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2020, month, day).unwrap();
+        let days = |date| async move {
+            fixtures()
+                .days_until_next_holiday_after("us", date)
+                .await
+                .unwrap()
+        };
+        assert_eq!(days(date(7, 1)).await, Some(3));
+        assert_eq!(days(date(7, 4)).await, Some(119));
+
+        let empty =
+            HolidayAPI::with_fixtures(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/empty"))
+                .unwrap();
+        let none = empty.days_until_next_holiday_after("us", date(7, 1)).await;
+        assert_eq!(none.unwrap(), None);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[tokio::test]
+    async fn test_workdays_with_weekend() {
+        let api = fixtures();
+        let date = |month, day| NaiveDate::from_ymd_opt(2020, month, day).unwrap();
+        let fri_sat = [Weekday::Fri, Weekday::Sat];
+
+        // July 2020 has 23 weekdays, minus Friday the 3rd, the observed
+        // Independence Day. With a Friday and Saturday weekend, it has 22
+        // Sunday to Thursday days, the holiday already being a day off.
+        let sat_sun = [Weekday::Sat, Weekday::Sun];
+        let workdays = api.workdays_with_weekend("us", date(7, 1), date(7, 31), &sat_sun);
+        assert_eq!(workdays.await.unwrap(), 22);
+        let workdays = api.workdays_with_weekend("us", date(7, 31), date(7, 1), &fri_sat);
+        assert_eq!(workdays.await.unwrap(), 22);
+
+        // Thursday the 2nd, then Sunday the 5th, skipping the weekend.
+        let workday = api.workday_with_weekend("us", date(7, 1), 2, &fri_sat);
+        assert_eq!(workday.await.unwrap(), date(7, 5));
+        let workday = api.workday_with_weekend("us", date(7, 6), -2, &fri_sat);
+        assert_eq!(workday.await.unwrap(), date(7, 2));
+        let workday = api.workday_with_weekend("us", date(7, 4), 0, &fri_sat);
+        assert_eq!(workday.await.unwrap(), date(7, 4));
+
+        let every_day = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ];
+        let workday = api.workday_with_weekend("us", date(7, 1), 2, &every_day);
+        assert!(matches!(
+            workday.await,
+            Err(HolidayAPIError::InvalidRequest(_))
+        ));
+    }
+}
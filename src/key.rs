@@ -0,0 +1,69 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+use crate::HolidayAPIError;
+
+/// Checks that a key is plausibly a valid one, a lowercase UUID, without
+/// constructing a client or sending a request, e.g. to validate a key read
+/// from the configuration at startup.
+///
+/// Only the format is checked: whether the key exists or has expired is only
+/// known once a request fails with `HolidayAPIError::InvalidOrExpiredKey`.
+/// The check is cheap, there is no point in caching its result.
+///
+/// # Examples
+/// ```
+/// use holidayapi_rust::prelude::*;
+///
+/// assert!(KeyValidator::is_valid("00000000-0000-0000-0000-000000000000"));
+/// assert!(KeyValidator::validate(" 00000000-0000-0000-0000-000000000000").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KeyValidator;
+
+impl KeyValidator {
+    fn regex() -> &'static Regex {
+        static UUID_REGEX: OnceLock<Regex> = OnceLock::new();
+        UUID_REGEX.get_or_init(|| {
+            Regex::new(r"^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$")
+                .expect("Regex is correct")
+        })
+    }
+
+    /// Whether the whole of `key` is a lowercase UUID.
+    pub fn is_valid(key: &str) -> bool {
+        Self::regex().is_match(key)
+    }
+
+    /// Fails with `HolidayAPIError::InvalidKeyFormat` unless the whole of
+    /// `key` is a lowercase UUID.
+    pub fn validate(key: &str) -> Result<(), HolidayAPIError> {
+        match Self::is_valid(key) {
+            true => Ok(()),
+            false => Err(HolidayAPIError::InvalidKeyFormat(key.into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_key() {
+        assert!(KeyValidator::validate("0b1e2c3d-4f5a-6b7c-8d9e-0f1a2b3c4d5e").is_ok());
+        for key in [
+            "",
+            "0B1E2C3D-4F5A-6B7C-8D9E-0F1A2B3C4D5E",
+            "0b1e2c3d4f5a6b7c8d9e0f1a2b3c4d5e",
+            "0b1e2c3d-4f5a-6b7c-8d9e-0f1a2b3c4d5",
+            "0b1e2c3d-4f5a-6b7c-8d9e-0f1a2b3c4d5e\n",
+        ] {
+            assert!(!KeyValidator::is_valid(key), "Should reject {:?}", key);
+            assert!(matches!(
+                KeyValidator::validate(key),
+                Err(HolidayAPIError::InvalidKeyFormat(invalid)) if invalid == key
+            ));
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use std::{fmt, str::FromStr};
+
+use crate::HolidayAPIError;
+
+/// A language code checked against the ISO 639-1 codes, which the API's
+/// languages are based on, e.g. for the `language` of a holidays request.
+///
+/// The crate ships no snapshot of the API's languages, which may change, so
+/// the codes are those of ISO 639-1, as `CountryCode` uses ISO 3166-1.
+///
+/// Parsing is case-insensitive, the code is kept in lowercase. Codes outside
+/// of ISO 639-1 can still be passed to the requests as a plain `&str`.
+///
+/// # Examples
+/// ```
+/// use holidayapi_rust::prelude::*;
+///
+/// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+/// let japanese: LanguageCode = "JA".parse().unwrap();
+/// let request = api.holidays("JP", 2020).language(japanese);
+///
+/// assert!(LanguageCode::try_from("jp").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LanguageCode(&'static str);
+
+impl LanguageCode {
+    /// Every known code, in alphabetical order.
+    pub const ALL: &'static [&'static str] = &[
+        "aa", "ab", "ae", "af", "ak", "am", "an", "ar", "as", "av", "ay", "az", "ba", "be", "bg",
+        "bi", "bm", "bn", "bo", "br", "bs", "ca", "ce", "ch", "co", "cr", "cs", "cu", "cv", "cy",
+        "da", "de", "dv", "dz", "ee", "el", "en", "eo", "es", "et", "eu", "fa", "ff", "fi", "fj",
+        "fo", "fr", "fy", "ga", "gd", "gl", "gn", "gu", "gv", "ha", "he", "hi", "ho", "hr", "ht",
+        "hu", "hy", "hz", "ia", "id", "ie", "ig", "ii", "ik", "io", "is", "it", "iu", "ja", "jv",
+        "ka", "kg", "ki", "kj", "kk", "kl", "km", "kn", "ko", "kr", "ks", "ku", "kv", "kw", "ky",
+        "la", "lb", "lg", "li", "ln", "lo", "lt", "lu", "lv", "mg", "mh", "mi", "mk", "ml", "mn",
+        "mr", "ms", "mt", "my", "na", "nb", "nd", "ne", "ng", "nl", "nn", "no", "nr", "nv", "ny",
+        "oc", "oj", "om", "or", "os", "pa", "pi", "pl", "ps", "pt", "qu", "rm", "rn", "ro", "ru",
+        "rw", "sa", "sc", "sd", "se", "sg", "si", "sk", "sl", "sm", "sn", "so", "sq", "sr", "ss",
+        "st", "su", "sv", "sw", "ta", "te", "tg", "th", "ti", "tk", "tl", "tn", "to", "tr", "ts",
+        "tt", "tw", "ty", "ug", "uk", "ur", "uz", "ve", "vi", "vo", "wa", "wo", "xh", "yi", "yo",
+        "za", "zh", "zu",
+    ];
+
+    /// The code in lowercase, e.g. `ja`.
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl AsRef<str> for LanguageCode {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl fmt::Display for LanguageCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl FromStr for LanguageCode {
+    type Err = HolidayAPIError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .binary_search(&code.to_ascii_lowercase().as_str())
+            .map(|index| LanguageCode(Self::ALL[index]))
+            .map_err(|_| HolidayAPIError::InvalidLanguage(code.to_owned()))
+    }
+}
+
+impl TryFrom<&str> for LanguageCode {
+    type Error = HolidayAPIError;
+
+    fn try_from(code: &str) -> Result<Self, Self::Error> {
+        code.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_language() {
+        assert!(LanguageCode::ALL.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(LanguageCode::try_from("En").unwrap().as_str(), "en");
+        for code in ["jp", "", "eng", "pt-BR"] {
+            assert!(matches!(
+                LanguageCode::try_from(code),
+                Err(HolidayAPIError::InvalidLanguage(invalid)) if invalid == code
+            ));
+        }
+    }
+
+    #[test]
+    fn test_fixture_languages() {
+        let fixture = include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/fixtures/languages.json"
+        ));
+        let response: crate::responses::LanguagesResponse = serde_json::from_str(fixture).unwrap();
+        for language in response.languages {
+            assert!(
+                LanguageCode::try_from(language.code.as_str()).is_ok(),
+                "{}",
+                language.code
+            );
+        }
+    }
+}
@@ -13,34 +13,131 @@
 //!
 //! #[tokio::main]
 //! async fn main() {
-//! 	let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
-//! 	let request = api.holidays("us", 2021).month(10).day(20).public().upcoming();
-//! 	let response = request.get().await;
-//!		match response {
-//! 		Ok(_) => { /* */ },
-//! 		Err(_) => { /* */ },
-//! 	}
+//!     let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+//!     let request = api.holidays("us", 2021).month(10).day(20).public(true).upcoming();
+//!     let response = request.get().await;
+//!     match response {
+//!         Ok(_) => { /* */ },
+//!         Err(_) => { /* */ },
+//!     }
 //! }
 //! ```
 pub mod prelude;
 
+mod builder;
+mod cache;
+mod country;
+#[cfg(feature = "polars")]
+mod dataframe;
+mod helpers;
+mod key;
+mod language;
+mod rate_limit;
 mod requests;
 mod responses;
-use requests::Request;
+mod retry;
+mod singleflight;
+mod stream;
+#[cfg(feature = "tracing")]
+mod telemetry;
+mod utils;
+mod validation;
+mod version;
+use builder::{HolidayAPIBuilder, RedirectPolicy};
+use requests::{Request, RequestSpec};
 use responses::{
-    CountriesResponse, HolidaysResponse, LanguagesResponse, WorkdayResponse, WorkdaysResponse,
+    ApiError, ApiResponse, ApiWarning, CountriesResponse, Country, HolidaysResponse, Language,
+    LanguagesResponse, WorkdayResponse, WorkdaysResponse,
 };
-use serde_json::Value;
-use std::{collections::HashMap, error::Error, fmt};
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt, fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use validation::ValidationError;
+use version::ApiVersion;
 
-use regex::Regex;
 pub use reqwest::Response;
-use reqwest::Url;
+use reqwest::{StatusCode, Url};
+
+/// Placeholder for the key in `Debug` output and traces.
+pub(crate) const REDACTED: &str = "<redacted>";
 
-#[derive(Debug, Clone)]
+/// A client of the API, created with `HolidayAPI::new` or
+/// `HolidayAPI::builder`.
+///
+/// Clones are cheap and share the connection pool of the HTTP client, as
+/// well as the cache, singleflight, rate limit and `requests_made` counter,
+/// so clone it into each task or request handler rather than constructing a
+/// new one, which starts a new connection pool.
+#[derive(Clone)]
 pub struct HolidayAPI {
-    base_url: String,
+    base_url: Url,
     key: String,
+    client: reqwest::Client,
+    defaults: HashMap<String, String>,
+    fixtures: Option<PathBuf>,
+    max_response_bytes: Option<usize>,
+    singleflight: Option<Arc<singleflight::Singleflight>>,
+    rate_limit: Option<Arc<rate_limit::RateLimiter>>,
+    cache: Option<cache::CacheLayer>,
+    countries: Option<Arc<Vec<Country>>>,
+    languages: Option<Arc<Vec<Language>>>,
+    retry: Option<retry::RetryPolicy>,
+    requests_made: Arc<AtomicU64>,
+    strict_warnings: bool,
+}
+
+impl fmt::Debug for HolidayAPI {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HolidayAPI")
+            .field("base_url", &self.base_url.as_str())
+            .field("key", &REDACTED)
+            .field("defaults", &self.defaults)
+            .field("fixtures", &self.fixtures)
+            .field("max_response_bytes", &self.max_response_bytes)
+            .field("singleflight", &self.singleflight.is_some())
+            .field("rate_limit", &self.rate_limit)
+            .field("cache", &self.cache)
+            .field("retry", &self.retry)
+            .field("requests_made", &self.requests_made())
+            .field("strict_warnings", &self.strict_warnings)
+            .field(
+                "countries",
+                &self.countries.as_ref().map(|countries| countries.len()),
+            )
+            .field(
+                "languages",
+                &self.languages.as_ref().map(|languages| languages.len()),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+/// Two APIs are equal when they send requests to the same base url with the
+/// same key, whatever their other settings.
+impl PartialEq for HolidayAPI {
+    fn eq(&self, other: &Self) -> bool {
+        self.base_url == other.base_url && self.key == other.key
+    }
+}
+
+impl Eq for HolidayAPI {}
+
+/// Settings of a single request that are not sent as parameters.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RequestOptions {
+    pub(crate) timeout: Option<Duration>,
+    /// Extra headers, checked by `Request::validate`.
+    pub(crate) headers: Vec<(String, String)>,
+    /// Set by `Request::correlation_id`.
+    pub(crate) correlation_id: Option<String>,
 }
 
 #[derive(Debug)]
@@ -48,7 +145,36 @@ pub enum HolidayAPIError {
     InvalidKeyFormat(String),
     InvalidOrExpiredKey(String),
     InvalidVersion(String),
+    /// A country code that is not known, see `CountryCode`.
+    InvalidCountry(String),
+    /// A language code that is not known, see `LanguageCode`.
+    InvalidLanguage(String),
+    InvalidResponse(String),
+    IoError(std::io::Error, String),
+    Parse {
+        source: serde_json::Error,
+        body_snippet: String,
+    },
     RequestError(reqwest::Error, String),
+    ResponseTooLarge(usize),
+    /// A successful response without a body to parse, e.g. a truncated one.
+    EmptyResponse,
+    /// A response with a warning, in strict mode, see
+    /// `HolidayAPIBuilder::strict_warnings`.
+    Warning(ApiWarning),
+    /// The response has a content type that does not match the requested
+    /// format, e.g. an HTML page from a proxy.
+    UnexpectedContentType(String),
+    /// A request cannot be built from the given settings.
+    InvalidRequest(String),
+    /// A request was made for a response format that cannot be parsed.
+    UnsupportedFormat(String),
+    /// A request was not sent because of its parameters, see
+    /// `Request::validate`.
+    Validation(ValidationError),
+    /// The error of a request shared with other identical requests, see
+    /// `HolidayAPIBuilder::singleflight`.
+    Shared(Arc<HolidayAPIError>),
 }
 
 impl fmt::Display for HolidayAPIError {
@@ -56,51 +182,149 @@ impl fmt::Display for HolidayAPIError {
         match self {
             HolidayAPIError::InvalidKeyFormat(key) => write!(f, "Invalid key: {}", key),
             HolidayAPIError::InvalidVersion(version) => write!(f, "Invalid version: {}", version),
+            HolidayAPIError::InvalidCountry(code) => write!(f, "Invalid country: {}", code),
+            HolidayAPIError::InvalidLanguage(code) => write!(f, "Invalid language: {}", code),
+            HolidayAPIError::InvalidResponse(reason) => write!(f, "Invalid response: {}", reason),
+            HolidayAPIError::IoError(err, path) => write!(f, "{}: {}", path, err),
+            HolidayAPIError::Parse {
+                source,
+                body_snippet,
+            } => write!(f, "Invalid response: {}\nBody: '{}'", source, body_snippet),
+            HolidayAPIError::ResponseTooLarge(max) => {
+                write!(f, "Response is larger than the {} bytes limit", max)
+            }
+            HolidayAPIError::EmptyResponse => write!(f, "Response body is empty"),
+            HolidayAPIError::Warning(warning) => match warning {
+                ApiWarning::Deprecation(deprecation) => {
+                    write!(f, "Warning: {}", deprecation.message)
+                }
+                ApiWarning::Other(warning) => write!(f, "Warning: {}", warning),
+            },
+            HolidayAPIError::UnexpectedContentType(content_type) => {
+                write!(f, "Unexpected content type: {}", content_type)
+            }
+            HolidayAPIError::InvalidRequest(reason) => write!(f, "Invalid request: {}", reason),
+            HolidayAPIError::Validation(err) => write!(f, "Invalid request: {}", err),
+            HolidayAPIError::UnsupportedFormat(format) => {
+                write!(f, "Unsupported format: {}, only json can be parsed", format)
+            }
             HolidayAPIError::InvalidOrExpiredKey(key) => {
                 write!(f, "Invalid or expired key: {}", key)
             }
             HolidayAPIError::RequestError(req, err) => {
-                write!(
-                    f,
-                    "{}: {}\nRaw url: '{}'",
-                    req.status().unwrap(),
-                    err,
-                    req.url().unwrap(),
-                )
+                let cause = match req.status() {
+                    Some(status) => status.to_string(),
+                    None => req.to_string(),
+                };
+                match (cause.is_empty(), err.is_empty()) {
+                    (false, false) => write!(f, "{}: {}", cause, err)?,
+                    _ => write!(f, "{}{}", cause, err)?,
+                }
+                if let Some(url) = req.url() {
+                    write!(f, "\nRaw url: '{}'", url)?;
+                }
+                Ok(())
             }
+            HolidayAPIError::Shared(err) => err.fmt(f),
+        }
+    }
+}
+impl Error for HolidayAPIError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            HolidayAPIError::IoError(err, _) => Some(err),
+            HolidayAPIError::Parse { source, .. } => Some(source),
+            HolidayAPIError::RequestError(err, _) => Some(err),
+            HolidayAPIError::Validation(err) => Some(err),
+            HolidayAPIError::Shared(err) => err.source(),
+            _ => None,
+        }
+    }
+}
+
+impl From<ValidationError> for HolidayAPIError {
+    fn from(err: ValidationError) -> Self {
+        HolidayAPIError::Validation(err)
+    }
+}
+
+impl From<HolidayAPIError> for std::io::Error {
+    fn from(err: HolidayAPIError) -> Self {
+        match err {
+            HolidayAPIError::IoError(err, _) => err,
+            err => std::io::Error::other(err),
+        }
+    }
+}
+
+impl HolidayAPIError {
+    /// Longest part of a response body included in a `Parse` error.
+    const SNIPPET_LENGTH: usize = 256;
+
+    /// The error for a body that failed to parse, `EmptyResponse` when there
+    /// was nothing to parse.
+    pub(crate) fn parse(source: serde_json::Error, body: &str) -> Self {
+        if body.trim().is_empty() {
+            return HolidayAPIError::EmptyResponse;
+        }
+        let mut body_snippet: String = body.chars().take(Self::SNIPPET_LENGTH).collect();
+        if body_snippet.len() < body.len() {
+            body_snippet.push_str("...");
+        }
+        HolidayAPIError::Parse {
+            source,
+            body_snippet,
         }
     }
 }
-impl Error for HolidayAPIError {}
 
 impl HolidayAPI {
+    /// Same as `KeyValidator::validate`.
     pub fn is_valid_key(key: &str) -> Result<(), HolidayAPIError> {
-        let uuid_regex =
-            Regex::new(r"[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}")
-                .expect("Regex is correct");
+        key::KeyValidator::validate(key)
+    }
 
-        if uuid_regex.is_match(key) {
-            Ok(())
-        } else {
-            Err(HolidayAPIError::InvalidKeyFormat(key.into()))
-        }
+    /// API versions accepted by `with_version` and `HolidayAPIBuilder::version`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// assert!(HolidayAPI::supported_versions().contains(&ApiVersion::V1));
+    /// ```
+    pub fn supported_versions() -> &'static [ApiVersion] {
+        ApiVersion::ALL
     }
 
     pub fn is_valid_version(version: &i32) -> Result<(), HolidayAPIError> {
-        let valid_versions = [1];
-        if !valid_versions.contains(version) {
-            Err(HolidayAPIError::InvalidVersion(format!(
-                "Invalid version: {}, please choose: {:?}",
-                version, valid_versions
-            )))
-        } else {
-            Ok(())
-        }
+        ApiVersion::try_from(*version).map(|_| ())
     }
-    fn construct_api(key: &str, version: i32) -> HolidayAPI {
+
+    fn default_client() -> Result<reqwest::Client, HolidayAPIError> {
+        reqwest::Client::builder()
+            .redirect(RedirectPolicy::default().to_reqwest())
+            .build()
+            .map_err(|e| HolidayAPIError::RequestError(e, "Failed to build the HTTP client".into()))
+    }
+
+    fn construct_api(key: &str, version: ApiVersion, client: reqwest::Client) -> HolidayAPI {
         HolidayAPI {
-            base_url: format!("https://holidayapi.com/v{}/", version),
+            base_url: Url::parse(&format!("https://holidayapi.com/{}/", version.as_str()))
+                .expect("Base url is valid"),
             key: key.to_owned(),
+            client,
+            defaults: HashMap::new(),
+            fixtures: None,
+            max_response_bytes: None,
+            singleflight: None,
+            rate_limit: None,
+            cache: None,
+            countries: None,
+            languages: None,
+            retry: None,
+            requests_made: Arc::default(),
+            strict_warnings: false,
         }
     }
     /// Construct a new holiday API
@@ -121,7 +345,11 @@ impl HolidayAPI {
     pub fn new(key: &str) -> Result<HolidayAPI, HolidayAPIError> {
         Self::is_valid_key(key)?;
 
-        Ok(Self::construct_api(key, 1))
+        Ok(Self::construct_api(
+            key,
+            ApiVersion::default(),
+            Self::default_client()?,
+        ))
     }
 
     /// Construct a new holiday API
@@ -129,7 +357,7 @@ impl HolidayAPI {
     /// # Errors
     ///
     /// Will return an `Err` if the given key is not plausibly a valid one. Or the api version is invalid.
-    /// Current valid versions: `[1]`
+    /// Current valid versions: `HolidayAPI::supported_versions()`
     ///
     /// # Examples
     ///
@@ -139,12 +367,373 @@ impl HolidayAPI {
     /// use holidayapi_rust::prelude::*;
     ///
     /// let api = HolidayAPI::with_version("00000000-0000-0000-0000-000000000000", 1).unwrap();
+    /// let api = HolidayAPI::with_version("00000000-0000-0000-0000-000000000000", "v1").unwrap();
+    /// let api =
+    ///     HolidayAPI::with_version("00000000-0000-0000-0000-000000000000", ApiVersion::V1).unwrap();
     /// ```
-    pub fn with_version(key: &str, version: i32) -> Result<HolidayAPI, HolidayAPIError> {
+    pub fn with_version<V>(key: &str, version: V) -> Result<HolidayAPI, HolidayAPIError>
+    where
+        V: TryInto<ApiVersion>,
+        V::Error: Into<HolidayAPIError>,
+    {
         Self::is_valid_key(key)?;
-        Self::is_valid_version(&version)?;
+        let version = version.try_into().map_err(Into::into)?;
 
-        Ok(Self::construct_api(key, version))
+        Ok(Self::construct_api(key, version, Self::default_client()?))
+    }
+
+    /// Construct a new holiday API that sends requests to `base_url` instead of
+    /// `https://holidayapi.com/v1/`, e.g. an internal gateway.
+    ///
+    /// `base_url` replaces the versioned url as a whole, so it should include
+    /// the version, e.g. `https://gw.internal/holidayapi/v1/`. A missing
+    /// trailing slash is tolerated.
+    ///
+    /// # Errors
+    ///
+    /// Will return an `Err` if the given key is not plausibly a valid one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    /// use reqwest::Url;
+    ///
+    /// let base_url = Url::parse("https://gw.internal/holidayapi/v1/").unwrap();
+    /// let api = HolidayAPI::with_base_url("00000000-0000-0000-0000-000000000000", base_url).unwrap();
+    /// ```
+    pub fn with_base_url(key: &str, base_url: Url) -> Result<HolidayAPI, HolidayAPIError> {
+        Self::builder(key).base_url(base_url).build()
+    }
+
+    /// Construct a holiday API for tests, sending requests to `base_url`, e.g.
+    /// a mock server, with any `key`, such as `"test"`.
+    ///
+    /// Unlike `with_base_url`, the key is not validated. Only available with
+    /// the `test-util` feature, enable it in `[dev-dependencies]` only.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    /// use reqwest::Url;
+    ///
+    /// let api = HolidayAPI::new_unchecked("test", Url::parse("http://127.0.0.1:8080/v1/").unwrap());
+    /// ```
+    #[cfg(feature = "test-util")]
+    pub fn new_unchecked(key: &str, base_url: Url) -> HolidayAPI {
+        let client = Self::default_client().expect("Default HTTP client to build");
+        let mut api = Self::construct_api(key, ApiVersion::default(), client);
+        api.base_url = base_url;
+        api
+    }
+
+    /// Start building a holiday API with custom client settings, e.g. a timeout.
+    ///
+    /// The key and the other settings are validated by `HolidayAPIBuilder::build`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// let api = HolidayAPI::builder("00000000-0000-0000-0000-000000000000")
+    ///     .timeout(Duration::from_secs(10))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder(key: &str) -> HolidayAPIBuilder {
+        HolidayAPIBuilder::new(key)
+    }
+
+    /// Construct a new holiday API with the key stored in a file, e.g. a
+    /// mounted Kubernetes secret. Surrounding whitespace and newlines are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Will return an `Err` if the file cannot be read or does not contain a
+    /// plausibly valid key.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let api = HolidayAPI::from_key_file("/run/secrets/holidayapi-key").unwrap();
+    /// ```
+    pub fn from_key_file(path: impl AsRef<Path>) -> Result<HolidayAPI, HolidayAPIError> {
+        let path = path.as_ref();
+        let key = fs::read_to_string(path)
+            .map_err(|e| HolidayAPIError::IoError(e, path.display().to_string()))?;
+
+        Self::new(key.trim())
+    }
+
+    /// Construct a holiday API that serves responses from local fixture files
+    /// instead of the network, e.g. to run examples and tests without a key.
+    ///
+    /// Every request to an endpoint is answered with the content of
+    /// `<dir>/<endpoint>.json`, e.g. `holidays.json` or `countries.json`,
+    /// regardless of its parameters. The crate ships a set of fixtures in its
+    /// `fixtures` directory.
+    ///
+    /// # Errors
+    ///
+    /// Will return an `Err` if `dir` is not a readable directory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let api = HolidayAPI::with_fixtures(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures")).unwrap();
+    /// let holidays = api.holidays("us", 2020).get().await.unwrap();
+    /// assert_eq!(holidays[0].name, "New Year's Day");
+    /// # }
+    /// ```
+    pub fn with_fixtures(dir: impl AsRef<Path>) -> Result<HolidayAPI, HolidayAPIError> {
+        let dir = dir.as_ref();
+        let metadata = fs::metadata(dir)
+            .map_err(|e| HolidayAPIError::IoError(e, dir.display().to_string()))?;
+        if !metadata.is_dir() {
+            return Err(HolidayAPIError::IoError(
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("{} is not a directory", dir.display()),
+                ),
+                dir.display().to_string(),
+            ));
+        }
+
+        let mut api = Self::construct_api(
+            "00000000-0000-0000-0000-000000000000",
+            ApiVersion::default(),
+            Self::default_client()?,
+        );
+        api.fixtures = Some(dir.to_owned());
+        Ok(api)
+    }
+
+    fn fixture_response(dir: &Path, endpoint: &str) -> Result<Response, HolidayAPIError> {
+        let path = dir.join(format!("{}.json", endpoint.to_ascii_lowercase()));
+        let body = fs::read_to_string(&path)
+            .map_err(|e| HolidayAPIError::IoError(e, path.display().to_string()))?;
+        let response = http::Response::builder()
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .expect("Fixture response is valid");
+        Ok(response.into())
+    }
+
+    /// Set a parameter that is sent with every request made through this api.
+    ///
+    /// Parameters set on an individual request take precedence over defaults.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000")
+    ///     .unwrap()
+    ///     .with_default_param("language", "ja");
+    /// // Requested in english despite the default.
+    /// let request = api.holidays("jp", 2020).language("en");
+    /// ```
+    pub fn with_default_param(&mut self, key: &str, value: &str) -> Self {
+        self.defaults.insert(key.into(), value.into());
+        self.to_owned()
+    }
+
+    /// Abort reading any response body larger than `max` bytes.
+    ///
+    /// Responses are unlimited by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000")
+    ///     .unwrap()
+    ///     .with_max_response_bytes(10 * 1024 * 1024);
+    /// ```
+    pub fn with_max_response_bytes(&mut self, max: usize) -> Self {
+        self.max_response_bytes = Some(max);
+        self.to_owned()
+    }
+
+    /// Reads the whole body of `response` as text, see `read_bytes`.
+    pub(crate) async fn read_body(&self, response: Response) -> Result<String, HolidayAPIError> {
+        String::from_utf8(self.read_bytes(response).await?)
+            .map_err(|_| HolidayAPIError::InvalidResponse("Response is not valid UTF-8".into()))
+    }
+
+    /// Reads the whole body of `response`, enforcing `max_response_bytes`.
+    pub(crate) async fn read_bytes(
+        &self,
+        mut response: Response,
+    ) -> Result<Vec<u8>, HolidayAPIError> {
+        let check_size = |len: usize| match self.max_response_bytes {
+            Some(max) if len > max => Err(HolidayAPIError::ResponseTooLarge(max)),
+            _ => Ok(()),
+        };
+        check_size(
+            response
+                .content_length()
+                .map_or(0, |len| usize::try_from(len).unwrap_or(usize::MAX)),
+        )?;
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| HolidayAPIError::RequestError(e, "".into()))?
+        {
+            check_size(body.len() + chunk.len())?;
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+
+    /// Sends a request and reads its body. Serves it from the cache when
+    /// one is set, and shares the call with identical requests in flight when
+    /// singleflight is enabled.
+    pub(crate) async fn fetch_body(
+        &self,
+        endpoint: &str,
+        parameters: HashMap<String, String>,
+        options: &RequestOptions,
+    ) -> Result<String, HolidayAPIError> {
+        if self.cache.is_none() && self.singleflight.is_none() {
+            return self.fetch_uncached(endpoint, parameters, options).await;
+        }
+
+        let key = self.cache_key(endpoint, &parameters);
+        if let Some(cache) = &self.cache {
+            match cache.lookup(&key) {
+                cache::Lookup::Fresh(body) => return Ok(body),
+                cache::Lookup::Stale(body) => {
+                    self.revalidate(cache, key, endpoint, parameters, options);
+                    return Ok(body);
+                }
+                cache::Lookup::Miss => {}
+            }
+        }
+
+        let body = match &self.singleflight {
+            Some(singleflight) => {
+                let api = self.clone();
+                let endpoint = endpoint.to_owned();
+                let options = options.clone();
+                let fetch =
+                    async move { api.fetch_uncached(&endpoint, parameters, &options).await };
+                singleflight.run(key.clone(), fetch).await?
+            }
+            None => self.fetch_uncached(endpoint, parameters, options).await?,
+        };
+        if let Some(cache) = &self.cache {
+            cache.set(&key, &body);
+        }
+        Ok(body)
+    }
+
+    /// Refreshes the cached body of `key` in the background, unless it is
+    /// already being refreshed. A failed refresh leaves the stale body.
+    fn revalidate(
+        &self,
+        cache: &cache::CacheLayer,
+        key: String,
+        endpoint: &str,
+        parameters: HashMap<String, String>,
+        options: &RequestOptions,
+    ) {
+        if !cache.start_refresh(&key) {
+            return;
+        }
+        let api = self.clone();
+        let cache = cache.clone();
+        let endpoint = endpoint.to_owned();
+        let options = options.clone();
+        tokio::spawn(async move {
+            if let Ok(body) = api.fetch_uncached(&endpoint, parameters, &options).await {
+                cache.set(&key, &body);
+            }
+            cache.finish_refresh(&key);
+        });
+    }
+
+    async fn fetch_uncached(
+        &self,
+        endpoint: &str,
+        parameters: HashMap<String, String>,
+        options: &RequestOptions,
+    ) -> Result<String, HolidayAPIError> {
+        let format = parameters.get("format").cloned();
+        let response = self.request(endpoint, parameters, options).await?;
+        Self::check_content_type(&response, format.as_deref())?;
+        self.read_body(response).await
+    }
+
+    /// Fails when the response obviously is not in the requested `format`,
+    /// e.g. a gateway's HTML error page, instead of letting parsing fail.
+    ///
+    /// JSON responses must have a JSON content type. Other formats are only
+    /// rejected when HTML is received, as their content types vary.
+    pub(crate) fn check_content_type(
+        response: &Response,
+        format: Option<&str>,
+    ) -> Result<(), HolidayAPIError> {
+        let Some(content_type) = response.headers().get(reqwest::header::CONTENT_TYPE) else {
+            return Ok(());
+        };
+        let content_type = content_type
+            .to_str()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let json = format.is_none_or(|format| format.eq_ignore_ascii_case("json"));
+
+        if content_type.starts_with("text/html") || (json && !content_type.contains("json")) {
+            Err(HolidayAPIError::UnexpectedContentType(content_type))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Identifies a request by its url without the key: the base url, which
+    /// holds the version, the endpoint and the encoded parameters, defaults
+    /// included, regardless of their order.
+    fn cache_key(&self, endpoint: &str, parameters: &HashMap<String, String>) -> String {
+        let mut merged: BTreeMap<_, _> = self.defaults.iter().collect();
+        merged.extend(parameters);
+        let mut url = self.endpoint_url(endpoint);
+        url.query_pairs_mut().extend_pairs(merged);
+        url.into()
+    }
+
+    fn request_url(&self, endpoint: &str, parameters: HashMap<String, String>) -> Url {
+        let mut merged = self.defaults.clone();
+        merged.extend(parameters);
+
+        let mut url = self.endpoint_url(endpoint);
+        url.query_pairs_mut()
+            .append_pair("key", &self.key)
+            .extend_pairs(merged);
+        url
+    }
+
+    fn endpoint_url(&self, endpoint: &str) -> Url {
+        // Without a trailing slash, `join` would replace the last segment of
+        // the base path, and a leading slash in the endpoint would drop it all.
+        let mut base = self.base_url.clone();
+        if !base.path().ends_with('/') {
+            base.set_path(&format!("{}/", base.path()));
+        }
+        let endpoint = endpoint.trim_start_matches('/').to_ascii_lowercase();
+        base.join(&endpoint).expect("Endpoint is a valid path")
     }
 
     /// Make a custom request.
@@ -164,33 +753,211 @@ impl HolidayAPI {
         endpoint: &str,
         parameters: HashMap<String, String>,
     ) -> Result<Response, HolidayAPIError> {
-        let client = reqwest::Client::new();
-        let url = Url::parse(self.base_url.as_str()).unwrap();
-        let url = url.join(endpoint.to_ascii_lowercase().as_str()).unwrap();
-        let url = Url::parse_with_params(&format!("{}?key={}", url, self.key), parameters)
-            .expect("Parameters are invalid");
-        let response = client
-            .get(url)
+        self.request(endpoint, parameters, &RequestOptions::default())
+            .await
+    }
+
+    /// Escape hatch for endpoints this library does not cover yet: sends a
+    /// request to `endpoint`, relative to the base url, and returns the raw
+    /// body.
+    ///
+    /// `parameters` are sent as is, along with the key and the default
+    /// parameters. The cache and singleflight apply as for typed requests.
+    ///
+    /// # Errors
+    ///
+    /// Will return `HolidayAPIError::InvalidRequest` if `endpoint` is not a
+    /// plain relative path, e.g. empty, absolute or containing `..`, `?` or
+    /// `#`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    /// use std::collections::HashMap;
+    ///
+    /// async fn raw(api: HolidayAPI) -> Result<String, HolidayAPIError> {
+    ///     let parameters = HashMap::from([("country".to_string(), "US".to_string())]);
+    ///     api.raw_request("countries", parameters).await
+    /// }
+    /// ```
+    pub async fn raw_request(
+        &self,
+        endpoint: &str,
+        parameters: HashMap<String, String>,
+    ) -> Result<String, HolidayAPIError> {
+        let endpoint = endpoint.trim_start_matches('/');
+        let invalid = endpoint.is_empty()
+            || endpoint.contains("://")
+            || endpoint.contains(['?', '#', '\\'])
+            || endpoint.split('/').any(|segment| segment == "..");
+        if invalid {
+            return Err(HolidayAPIError::InvalidRequest(format!(
+                "Invalid endpoint path: {:?}",
+                endpoint
+            )));
+        }
+        self.fetch_body(endpoint, parameters, &RequestOptions::default())
+            .await
+    }
+
+    pub(crate) async fn request(
+        &self,
+        endpoint: &str,
+        parameters: HashMap<String, String>,
+        options: &RequestOptions,
+    ) -> Result<Response, HolidayAPIError> {
+        #[cfg(feature = "tracing")]
+        {
+            use tracing::Instrument;
+
+            let span = telemetry::request_span(
+                endpoint,
+                &parameters,
+                &self.key,
+                options.correlation_id.as_deref(),
+            );
+            let result = self
+                .send_with_retry(endpoint, parameters, options)
+                .instrument(span.clone())
+                .await;
+            telemetry::record_outcome(&span, &result, &self.key);
+            result
+        }
+        #[cfg(not(feature = "tracing"))]
+        self.send_with_retry(endpoint, parameters, options).await
+    }
+
+    async fn send_with_retry(
+        &self,
+        endpoint: &str,
+        parameters: HashMap<String, String>,
+        options: &RequestOptions,
+    ) -> Result<Response, HolidayAPIError> {
+        let Some(retry) = &self.retry else {
+            return self.send_request(endpoint, parameters, options).await;
+        };
+        for attempt in 0.. {
+            let err = match self
+                .send_request(endpoint, parameters.clone(), options)
+                .await
+            {
+                Ok(response) => return Ok(response),
+                Err(err) => err,
+            };
+            match retry.delay(attempt, &err) {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return Err(err),
+            }
+        }
+        unreachable!("Retries are bounded by the policy")
+    }
+
+    async fn send_request(
+        &self,
+        endpoint: &str,
+        parameters: HashMap<String, String>,
+        options: &RequestOptions,
+    ) -> Result<Response, HolidayAPIError> {
+        if let Some(dir) = &self.fixtures {
+            return Self::fixture_response(dir, endpoint);
+        }
+
+        if let Some(rate_limit) = &self.rate_limit {
+            rate_limit.acquire().await;
+        }
+        let url = self.request_url(endpoint, parameters);
+        let mut request = self.client.get(url);
+        if let Some(timeout) = options.timeout {
+            request = request.timeout(timeout);
+        }
+        for (name, value) in &options.headers {
+            request = request.header(name, value);
+        }
+        let response = request
             .send()
             .await
             .map_err(|e| HolidayAPIError::RequestError(e, "".to_string()))?;
+        self.requests_made.fetch_add(1, Ordering::Relaxed);
 
         match response.error_for_status_ref() {
             Ok(_) => Ok(response),
             Err(err) => {
-                let val = serde_json::from_str::<Value>(&response.text().await.unwrap())
-                    .expect("Error response to be JSON");
-                let o = val.as_object();
-                let error = o.and_then(|o| o.get("error")).unwrap();
-
-                Err(HolidayAPIError::RequestError(
-                    err,
-                    error.as_str().unwrap().into(),
-                ))
+                let status = response.status();
+                let body = match self.read_bytes(response).await {
+                    Ok(body) => String::from_utf8_lossy(&body).into_owned(),
+                    Err(err @ HolidayAPIError::ResponseTooLarge(_)) => return Err(err),
+                    Err(_) => String::new(),
+                };
+                let error = Self::error_message(status, &body);
+                let unauthorized = status == StatusCode::UNAUTHORIZED;
+
+                if unauthorized {
+                    Err(HolidayAPIError::InvalidOrExpiredKey(error))
+                } else {
+                    Err(HolidayAPIError::RequestError(err, error))
+                }
             }
         }
     }
 
+    /// Extracts the message of an error response, falling back to the
+    /// status reason when the body is not an `ApiError`, e.g. a proxy's page.
+    fn error_message(status: StatusCode, body: &str) -> String {
+        match serde_json::from_str::<ApiError>(body) {
+            Ok(api_error) => api_error.error,
+            Err(_) => status.canonical_reason().unwrap_or_default().to_string(),
+        }
+    }
+
+    /// Number of requests sent by this API and its clones that got a response,
+    /// error responses and retries included, e.g. to keep track of the quota
+    /// spent by a test or a batch job.
+    ///
+    /// Responses served from the cache, fixtures or another identical
+    /// request (see `HolidayAPIBuilder::singleflight`) are not counted.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn spent(api: HolidayAPI) -> Result<u64, HolidayAPIError> {
+    ///     let before = api.requests_made();
+    ///     api.holidays("us", 2020).get().await?;
+    ///     Ok(api.requests_made() - before)
+    /// }
+    /// ```
+    pub fn requests_made(&self) -> u64 {
+        self.requests_made.load(Ordering::Relaxed)
+    }
+
+    /// Checks that the API is reachable and accepts the key, returning the
+    /// round-trip time of the check.
+    ///
+    /// Makes the smallest request available, a `languages` request for a
+    /// single language, which still counts against your quota.
+    ///
+    /// # Errors
+    ///
+    /// Will return `HolidayAPIError::InvalidOrExpiredKey` if the key is
+    /// rejected, or `HolidayAPIError::RequestError` if the API is unreachable
+    /// or fails otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn ready(api: &HolidayAPI) -> bool {
+    ///     api.ping().await.is_ok()
+    /// }
+    /// ```
+    pub async fn ping(&self) -> Result<Duration, HolidayAPIError> {
+        let start = Instant::now();
+        self.languages().language("en").get_raw().await?;
+        Ok(start.elapsed())
+    }
+
     /// Generates a minimal `countries` request and returns it.
     ///
     /// # Examples
@@ -199,7 +966,7 @@ impl HolidayAPI {
     /// ```
     /// use holidayapi_rust::prelude::*;
     ///
-    ///	let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
     /// let request = api.countries();
     /// ```
     ///
@@ -207,8 +974,8 @@ impl HolidayAPI {
     /// ```
     /// use holidayapi_rust::prelude::*;
     ///
-    ///	let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
-    /// let specific_request = api.countries().search("united states").public();
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// let specific_request = api.countries().search("united states").public(true);
     /// ```
     pub fn countries(&self) -> Request<CountriesResponse> {
         Request::<CountriesResponse>::new(self)
@@ -216,13 +983,19 @@ impl HolidayAPI {
 
     /// Generates a minimal `holidays` request and returns it.
     ///
+    /// Holidays of future years are computed from the same rules as past
+    /// ones, the API has no parameter or response field telling historical
+    /// holidays apart from projected ones. Compare `Holiday::date` with today
+    /// if you need the distinction. Free plans only give access to the
+    /// previous year.
+    ///
     /// # Examples
     ///
     /// Basic usage
     /// ```
     /// use holidayapi_rust::prelude::*;
     ///
-    ///	let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
     /// let request = api.holidays("us", 2020);
     /// ```
     ///
@@ -230,7 +1003,7 @@ impl HolidayAPI {
     /// ```
     /// use holidayapi_rust::prelude::*;
     ///
-    ///	let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
     /// let specific_request = api.holidays("us", 2020).month(12).upcoming();
     /// ```
     pub fn holidays(&self, country: &str, year: i32) -> Request<HolidaysResponse> {
@@ -239,34 +1012,57 @@ impl HolidayAPI {
 
     /// Generates a minimal `workday` request and returns it.
     ///
+    /// The request is for the date `days` workdays after `start`, or before
+    /// it when `days` is negative, see `Request::days_before`. The API always
+    /// treats Saturday and Sunday as the weekend, see `workday_with_weekend`
+    /// (feature `chrono`) for other weekends.
+    ///
     /// # Examples
     ///
     /// Basic usage
     /// ```
     /// use holidayapi_rust::prelude::*;
     ///
-    ///	let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
     /// let request = api.workday("us","YYYY-MM-DD", 100);
     /// ```
     pub fn workday(&self, country: &str, start: &str, days: i32) -> Request<WorkdayResponse> {
-        Request::<WorkdayResponse>::new(self, country.into(), start, days)
+        Request::<WorkdayResponse>::new(self, country, start, days)
     }
 
     /// Generates a minimal `workdays` request and returns it.
     ///
+    /// The API always treats Saturday and Sunday as the weekend, see
+    /// `workdays_with_weekend` (feature `chrono`) for other weekends.
+    ///
     /// # Examples
     ///
     /// Basic usage
     /// ```
     /// use holidayapi_rust::prelude::*;
     ///
-    ///	let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
     /// let request = api.workdays("us", "YYYY-MM-DD", "YYYY-MM-DD");
     /// ```
     pub fn workdays(&self, country: &str, start: &str, days: &str) -> Request<WorkdaysResponse> {
         Request::<WorkdaysResponse>::new(self, country, start, days)
     }
 
+    /// Rebuilds a request saved with `Request::to_spec`, sent with the key of
+    /// this API.
+    ///
+    /// # Errors
+    ///
+    /// Will return `HolidayAPIError::InvalidRequest` if `spec` is for another
+    /// endpoint than the requested type, e.g. a `countries` spec loaded as a
+    /// `Request<HolidaysResponse>`.
+    pub fn load_request<T: ApiResponse>(
+        &self,
+        spec: RequestSpec,
+    ) -> Result<Request<T>, HolidayAPIError> {
+        Request::from_spec(self, spec)
+    }
+
     /// Generates a minimal `languages` request and returns it.
     ///
     /// # Examples
@@ -275,7 +1071,7 @@ impl HolidayAPI {
     /// ```
     /// use holidayapi_rust::prelude::*;
     ///
-    ///	let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
     /// let request = api.languages();
     /// ```
     ///
@@ -301,16 +1097,516 @@ mod tests {
 
     #[test]
     fn test_valid_key() {
-        match HolidayAPI::new(EXPIRED_KEY) {
-            Ok(_) => assert!(true),
-            Err(_) => unreachable!("Should not return an error on valid key"),
+        if HolidayAPI::new(EXPIRED_KEY).is_err() {
+            unreachable!("Should not return an error on valid key")
+        }
+        if HolidayAPI::new(INVALID_KEY).is_ok() {
+            unreachable!("Should return an error on invalid key")
+        }
+    }
+
+    #[test]
+    fn test_key_with_surrounding_garbage() {
+        let keys = [
+            format!("junk{}", EXPIRED_KEY),
+            format!("{}junk", EXPIRED_KEY),
+            format!("junk{}junk", EXPIRED_KEY),
+            format!(" {}", EXPIRED_KEY),
+            format!("{}\n", EXPIRED_KEY),
+        ];
+        for key in keys {
+            assert!(
+                HolidayAPI::is_valid_key(&key).is_err(),
+                "Should reject {:?}",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn test_builder() {
+        let api = HolidayAPI::builder(EXPIRED_KEY)
+            .timeout(Duration::from_secs(5))
+            .connect_timeout(Duration::from_secs(1))
+            .build();
+        assert!(api.is_ok());
+
+        let invalid_key = HolidayAPI::builder(INVALID_KEY).build();
+        assert!(matches!(
+            invalid_key,
+            Err(HolidayAPIError::InvalidKeyFormat(_))
+        ));
+        assert!(HolidayAPI::is_valid_version(&1).is_ok());
+        assert!(HolidayAPI::is_valid_version(&2).is_err());
+        let version = HolidayAPI::builder(EXPIRED_KEY).version("v1").build();
+        assert_eq!(
+            version.unwrap().base_url.as_str(),
+            "https://holidayapi.com/v1/"
+        );
+        let invalid_version = HolidayAPI::builder(EXPIRED_KEY).version(0).build();
+        assert!(matches!(
+            invalid_version,
+            Err(HolidayAPIError::InvalidVersion(_))
+        ));
+    }
+
+    #[test]
+    fn test_equality_and_debug() {
+        let api = HolidayAPI::new(EXPIRED_KEY).unwrap();
+        let same = HolidayAPI::builder(EXPIRED_KEY)
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        assert_eq!(api, same);
+        assert_eq!(api, api.clone().with_default_param("language", "ja"));
+        let gateway = Url::parse("https://gw.internal/holidayapi/v1/").unwrap();
+        assert_ne!(
+            api,
+            HolidayAPI::with_base_url(EXPIRED_KEY, gateway).unwrap()
+        );
+        assert_ne!(
+            api,
+            HolidayAPI::new("daaaaaab-aaaa-aaaa-aaaa-2aaaada37e15").unwrap()
+        );
+
+        let debug = format!("{:?} {:?}", api, api.holidays("US", 2020));
+        assert!(!debug.contains(EXPIRED_KEY), "{}", debug);
+        assert!(debug.contains(REDACTED));
+    }
+
+    #[test]
+    fn test_base_url_prefix() {
+        let cases = [
+            (
+                "https://holidayapi.com/v1/",
+                "https://holidayapi.com/v1/holidays",
+            ),
+            (
+                "https://gw.internal/holidayapi/v1/",
+                "https://gw.internal/holidayapi/v1/holidays",
+            ),
+            (
+                "https://gw.internal/holidayapi/v1",
+                "https://gw.internal/holidayapi/v1/holidays",
+            ),
+            ("http://localhost:8080", "http://localhost:8080/holidays"),
+        ];
+        for (base_url, expected) in cases {
+            let api =
+                HolidayAPI::with_base_url(EXPIRED_KEY, Url::parse(base_url).unwrap()).unwrap();
+            for endpoint in ["holidays", "/holidays", "Holidays"] {
+                let mut url = api.request_url(endpoint, HashMap::new());
+                url.set_query(None);
+                assert_eq!(url.as_str(), expected, "{} + {}", base_url, endpoint);
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_key_file() {
+        let path = std::env::temp_dir().join(format!("holidayapi-key-{}", std::process::id()));
+
+        fs::write(&path, format!("  {}\n", EXPIRED_KEY)).unwrap();
+        assert!(HolidayAPI::from_key_file(&path).is_ok());
+
+        fs::write(&path, INVALID_KEY).unwrap();
+        let invalid = HolidayAPI::from_key_file(&path);
+        assert!(matches!(invalid, Err(HolidayAPIError::InvalidKeyFormat(_))));
+
+        fs::remove_file(&path).unwrap();
+        let missing = HolidayAPI::from_key_file(&path);
+        assert!(matches!(missing, Err(HolidayAPIError::IoError(_, _))));
+    }
+
+    #[test]
+    fn test_default_params() {
+        let api = HolidayAPI::new(EXPIRED_KEY)
+            .unwrap()
+            .with_default_param("language", "ja")
+            .with_default_param("pretty", "true");
+        let parameters = HashMap::from([("language".to_string(), "en".to_string())]);
+        let url = api.request_url("holidays", parameters);
+        let query: HashMap<_, _> = url.query_pairs().into_owned().collect();
+
+        assert_eq!(query["key"], EXPIRED_KEY);
+        assert_eq!(query["language"], "en");
+        assert_eq!(query["pretty"], "true");
+    }
+
+    #[tokio::test]
+    async fn test_fixtures() {
+        let api =
+            HolidayAPI::with_fixtures(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures")).unwrap();
+        let countries = api.countries().get().await.unwrap();
+        assert_eq!(countries[1].code, "US");
+        assert_eq!(api.holidays("us", 2020).count().await.unwrap(), 4);
+        let workdays = api
+            .workdays("us", "2020-07-01", "2020-07-31")
+            .get()
+            .await
+            .unwrap();
+        assert_eq!(workdays, 21);
+
+        match HolidayAPI::with_fixtures("does-not-exist") {
+            Err(HolidayAPIError::IoError(_, path)) => assert_eq!(path, "does-not-exist"),
+            _ => unreachable!("Should return an error on a missing directory"),
         }
-        match HolidayAPI::new(INVALID_KEY) {
-            Ok(_) => unreachable!("Should return an error on invalid key"),
-            Err(_) => assert!(true),
+        let file = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+        match HolidayAPI::with_fixtures(file) {
+            Err(HolidayAPIError::IoError(err, path)) => {
+                assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+                assert!(err.to_string().contains(file), "{}", err);
+                assert_eq!(path, file);
+            }
+            _ => unreachable!("Should return an error on a file"),
         }
     }
 
+    #[tokio::test]
+    async fn test_max_response_bytes() {
+        let mut api =
+            HolidayAPI::with_fixtures(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures")).unwrap();
+        assert!(api.languages().get().await.is_ok());
+
+        let api = api.with_max_response_bytes(64);
+        let languages = api.languages().get().await;
+        assert!(matches!(
+            languages,
+            Err(HolidayAPIError::ResponseTooLarge(64))
+        ));
+    }
+
+    #[test]
+    fn test_error_conversions() {
+        fn boxed() -> Result<(), Box<dyn Error + Send + Sync>> {
+            HolidayAPI::new(INVALID_KEY)?;
+            Ok(())
+        }
+        let err = boxed().unwrap_err();
+        assert!(err.downcast_ref::<HolidayAPIError>().is_some());
+
+        let missing = HolidayAPI::from_key_file("does-not-exist").unwrap_err();
+        assert!(missing.source().is_some());
+        let io: std::io::Error = missing.into();
+        assert_eq!(io.kind(), std::io::ErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_country_codes_only() {
+        let codes = HolidayAPI::with_fixtures(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures"))
+            .unwrap()
+            .countries()
+            .search("a")
+            .codes_only()
+            .await
+            .unwrap();
+        assert_eq!(
+            codes,
+            [
+                ("JP".into(), "Japan".into()),
+                ("US".into(), "United States".into())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_skipped_holidays() {
+        let api =
+            HolidayAPI::with_fixtures(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures")).unwrap();
+        let skipped = |start, end| api.workdays("US", start, end).skipped_holidays();
+
+        let names = |holidays: Vec<responses::Holiday>| -> Vec<String> {
+            holidays.into_iter().map(|holiday| holiday.name).collect()
+        };
+        let second_half = skipped("2020-07-01", "2020-12-31").await.unwrap();
+        assert_eq!(names(second_half), ["Independence Day", "Christmas Day"]);
+        // Independence Day is observed on the 3rd.
+        let july = skipped("2020-07-31", "2020-07-04").await.unwrap();
+        assert!(july.is_empty());
+        assert!(matches!(
+            skipped("2020-07-01", "2020-07-32").await,
+            Err(HolidayAPIError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_sorted_map() {
+        let api =
+            HolidayAPI::with_fixtures(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures")).unwrap();
+        let map = api.holidays("us", 2020).get_sorted_map().await.unwrap();
+        let dates: Vec<_> = map.keys().map(String::as_str).collect();
+        assert_eq!(
+            dates,
+            ["2020-01-01", "2020-07-04", "2020-10-31", "2020-12-25"]
+        );
+        assert_eq!(map["2020-07-04"][0].name, "Independence Day");
+
+        let observed = api.holidays("us", 2020).observed().get_sorted_map();
+        assert!(observed.await.unwrap().contains_key("2020-07-03"));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_typed_dates() {
+        let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+        let date = |month, day| chrono::NaiveDate::from_ymd_opt(2020, month, day).unwrap();
+        let workdays = api
+            .workdays("US", "2020-01-01", "2020-01-31")
+            .start_date(date(7, 1))
+            .end_date(date(7, 31))
+            .to_spec();
+        assert_eq!(workdays.parameters["start"], "2020-07-01");
+        assert_eq!(workdays.parameters["end"], "2020-07-31");
+        let workday = api.workday("US", "", 4).start_date(date(12, 24)).to_spec();
+        assert_eq!(workday.parameters["start"], "2020-12-24");
+    }
+
+    #[test]
+    fn test_pretty() {
+        let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+        let pretty = api.languages().pretty(true);
+        assert_eq!(pretty.to_spec().parameters["pretty"], "true");
+        for compact in [
+            api.languages(),
+            pretty.clone().pretty(false),
+            pretty.clone().compact(),
+        ] {
+            assert!(!compact.to_spec().parameters.contains_key("pretty"));
+        }
+    }
+
+    #[test]
+    fn test_days_before() {
+        let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+        let days = |request: Request<WorkdayResponse>| request.to_spec().parameters["days"].clone();
+        assert_eq!(days(api.workday("US", "2020-07-31", -10)), "-10");
+        assert_eq!(
+            days(api.workday("US", "2020-07-31", 10).days_before(10)),
+            "-10"
+        );
+        assert_eq!(
+            days(api.workday("US", "2020-07-31", 10).days_before(0)),
+            "0"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_by_uuid() {
+        let api =
+            HolidayAPI::with_fixtures(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures")).unwrap();
+        let holidays = api.holidays("US", 2020).get().await.unwrap();
+        let by_uuid = api.holidays("US", 2020).get_by_uuid().await.unwrap();
+        assert_eq!(by_uuid.len(), holidays.len());
+        for holiday in &holidays {
+            assert_eq!(by_uuid[&holiday.uuid].name, holiday.name);
+        }
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+        let parameters = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect()
+        };
+        let key = |api: &HolidayAPI, pairs: &[(&str, &str)]| {
+            api.cache_key("holidays", &parameters(pairs))
+        };
+
+        let search = key(&api, &[("search", "a&b=c")]);
+        assert_ne!(search, key(&api, &[("search", "a"), ("b", "c")]));
+        assert_eq!(
+            key(&api, &[("year", "2020"), ("country", "US")]),
+            key(&api, &[("country", "US"), ("year", "2020")])
+        );
+        assert!(!search.contains(&api.key));
+
+        let other_base = HolidayAPI::with_base_url(
+            "00000000-0000-0000-0000-000000000000",
+            Url::parse("https://example.com/v1/").unwrap(),
+        )
+        .unwrap();
+        assert_ne!(search, key(&other_base, &[("search", "a&b=c")]));
+    }
+
+    #[test]
+    fn test_formats() {
+        for format in requests::Format::all() {
+            assert_eq!(format.as_str(), format.to_string());
+            assert_eq!(format.as_str().parse(), Ok(*format));
+        }
+    }
+
+    #[test]
+    fn test_public() {
+        let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+        let public = api.holidays("us", 2020).public(true);
+        assert_eq!(public.to_spec().parameters["public"], "true");
+        assert!(!public
+            .clone()
+            .public(false)
+            .to_spec()
+            .parameters
+            .contains_key("public"));
+        let countries = api.countries().public(true).public(false);
+        assert!(!countries.to_spec().parameters.contains_key("public"));
+    }
+
+    #[tokio::test]
+    async fn test_raw_request() {
+        let api =
+            HolidayAPI::with_fixtures(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures")).unwrap();
+        let body = api.raw_request("/languages", HashMap::new()).await.unwrap();
+        assert!(body.contains("Japanese"));
+
+        for endpoint in [
+            "",
+            "/",
+            "../v2/holidays",
+            "holidays?year=2020",
+            "https://example.com",
+        ] {
+            assert!(
+                matches!(
+                    api.raw_request(endpoint, HashMap::new()).await,
+                    Err(HolidayAPIError::InvalidRequest(_))
+                ),
+                "Should reject {:?}",
+                endpoint
+            );
+        }
+    }
+
+    #[test]
+    fn test_request_spec() {
+        let api = HolidayAPI::new(EXPIRED_KEY).unwrap();
+        let spec = api
+            .holidays("us", 2020)
+            .public(true)
+            .observed()
+            .timeout(Duration::from_secs(3))
+            .correlation_id("job-42")
+            .to_spec();
+        assert_eq!(spec.correlation_id.as_deref(), Some("job-42"));
+        let saved = serde_json::to_string(&spec).unwrap();
+        assert!(!saved.contains(EXPIRED_KEY));
+
+        let loaded: RequestSpec = serde_json::from_str(&saved).unwrap();
+        assert_eq!(loaded, spec);
+        let request: Request<HolidaysResponse> = api.load_request(loaded.clone()).unwrap();
+        assert_eq!(request.to_spec(), spec);
+
+        let countries = api.load_request::<CountriesResponse>(loaded);
+        assert!(matches!(countries, Err(HolidayAPIError::InvalidRequest(_))));
+
+        let minimal: RequestSpec =
+            serde_json::from_str(r#"{"endpoint": "languages", "parameters": {}}"#).unwrap();
+        assert!(api.load_request::<LanguagesResponse>(minimal).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_get_full_with_raw() {
+        let api =
+            HolidayAPI::with_fixtures(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures")).unwrap();
+        let (response, raw) = api
+            .workdays("US", "2020-07-01", "2020-07-31")
+            .get_full_with_raw()
+            .await
+            .unwrap();
+        assert_eq!(response.workdays, 21);
+        assert!(raw.contains(r#""workdays": 21"#), "{}", raw);
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_format() {
+        let api =
+            HolidayAPI::with_fixtures(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures")).unwrap();
+        assert!(api.holidays("us", 2020).format("JSON").get().await.is_ok());
+        assert!(matches!(
+            api.holidays("us", 2020).format(requests::Format::Xml).get().await,
+            Err(HolidayAPIError::UnsupportedFormat(format)) if format == "xml"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_validation_before_sending() {
+        let api =
+            HolidayAPI::with_fixtures(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures")).unwrap();
+        let request = api
+            .holidays("us", 2020)
+            .month(7)
+            .day(4)
+            .previous()
+            .upcoming();
+        assert!(matches!(
+            request.clone().get().await,
+            Err(HolidayAPIError::Validation(
+                ValidationError::PreviousAndUpcoming
+            ))
+        ));
+        assert!(request.clone().get_raw().await.is_err());
+        assert!(request.stream().await.is_err());
+        let workdays = api.workdays("us", "2020-07-01", "July 31st").get().await;
+        assert!(matches!(
+            workdays,
+            Err(HolidayAPIError::Validation(ValidationError::InvalidDate {
+                parameter: "end",
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_error_message() {
+        let body = r#"{"status": 429, "error": "Rate limit exceeded."}"#;
+        assert_eq!(
+            HolidayAPI::error_message(StatusCode::TOO_MANY_REQUESTS, body),
+            "Rate limit exceeded."
+        );
+        assert_eq!(
+            HolidayAPI::error_message(StatusCode::BAD_GATEWAY, "<html>Bad gateway</html>"),
+            "Bad Gateway"
+        );
+    }
+
+    #[test]
+    fn test_request_error_display() {
+        let source = reqwest::Client::new().get("not a url").build().unwrap_err();
+        assert!(source.status().is_none());
+        let cause = source.to_string();
+
+        let error = HolidayAPIError::RequestError(source, "".into());
+        assert_eq!(error.to_string().lines().next(), Some(cause.as_str()));
+    }
+
+    #[test]
+    fn test_parse_error_snippet() {
+        let body = format!("<html>{}</html>", "a".repeat(1000));
+        let source = serde_json::from_str::<serde_json::Value>(&body).unwrap_err();
+        match HolidayAPIError::parse(source, &body) {
+            HolidayAPIError::Parse { body_snippet, .. } => {
+                assert!(body_snippet.starts_with("<html>aaa"));
+                assert!(body_snippet.ends_with("..."));
+                assert_eq!(body_snippet.len(), HolidayAPIError::SNIPPET_LENGTH + 3);
+            }
+            _ => unreachable!("Should return a parse error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_no_matching_holidays() {
+        let api = HolidayAPI::with_fixtures(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/empty"))
+            .unwrap();
+        let holidays = api
+            .holidays("us", 2020)
+            .search("no such holiday")
+            .get()
+            .await;
+        assert!(holidays.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_countries() {
         let api = HolidayAPI::new(EXPIRED_KEY).unwrap();
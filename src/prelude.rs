@@ -1,3 +1,14 @@
+pub use crate::builder::*;
+pub use crate::cache::*;
+pub use crate::country::*;
+#[cfg(feature = "polars")]
+pub use crate::dataframe::*;
+pub use crate::key::*;
+pub use crate::language::*;
 pub use crate::requests::*;
 pub use crate::responses::*;
+pub use crate::retry::*;
+pub use crate::utils::*;
+pub use crate::validation::*;
+pub use crate::version::*;
 pub use crate::*;
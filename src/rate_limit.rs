@@ -0,0 +1,58 @@
+use std::{
+    sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+/// Paces requests to at most a number per second, spacing them evenly so
+/// that a burst is spread out rather than sent at once.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    interval: Duration,
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(requests_per_second: u32) -> Self {
+        RateLimiter {
+            interval: Duration::from_secs(1) / requests_per_second,
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Instant> {
+        // The instant is never left half updated, so a poisoned lock is still usable.
+        self.next.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Waits for the next free slot. Slots are handed out in call order, and
+    /// the lock is not held while waiting.
+    pub(crate) async fn acquire(&self) {
+        let wait = {
+            let mut next = self.lock();
+            let now = Instant::now();
+            let slot = (*next).max(now);
+            *next = slot + self.interval;
+            slot - now
+        };
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::future;
+
+    #[tokio::test]
+    async fn test_acquire() {
+        let limiter = RateLimiter::new(20);
+        let start = Instant::now();
+        limiter.acquire().await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+
+        future::join_all((0..4).map(|_| limiter.acquire())).await;
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+}
@@ -1,73 +1,515 @@
-use serde::de::DeserializeOwned;
+#[cfg(feature = "chrono")]
+use chrono::NaiveDate;
+use futures_util::Stream;
+use reqwest::header::HeaderMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use strum_macros::{AsRefStr, Display, EnumString, IntoStaticStr};
 
 use crate::{
     responses::{
-        CountriesResponse, Country, Date, Holiday, HolidaysResponse, Language, LanguagesResponse,
-        WorkdayResponse, WorkdaysResponse,
+        ApiResponse, CountriesResponse, Country, Date, Holiday, HolidaysResponse, Language,
+        LanguagesResponse, Pagination, WorkdayResponse, WorkdaysResponse,
     },
-    HolidayAPI, HolidayAPIError,
+    stream,
+    validation::{self, ValidationError},
+    HolidayAPI, HolidayAPIError, RequestOptions, Response,
 };
-use std::{collections::HashMap, marker::PhantomData};
+use std::{
+    borrow::Cow,
+    collections::{hash_map::Entry, BTreeMap, HashMap},
+    marker::PhantomData,
+    str::FromStr,
+    time::Duration,
+};
+
+/// Response formats supported by the API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, AsRefStr, Display, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum Format {
+    Csv,
+    Json,
+    Php,
+    Tsv,
+    Xml,
+    Yaml,
+}
+
+impl Format {
+    /// Every format, in alphabetical order, e.g. to list them in a picker.
+    /// Parse a choice back with `str::parse`, which ignores case.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let names: Vec<&str> = Format::all().iter().map(Format::as_str).collect();
+    /// assert_eq!(names, ["csv", "json", "php", "tsv", "xml", "yaml"]);
+    /// assert_eq!("YAML".parse::<Format>(), Ok(Format::Yaml));
+    /// ```
+    pub fn all() -> &'static [Format] {
+        &[
+            Format::Csv,
+            Format::Json,
+            Format::Php,
+            Format::Tsv,
+            Format::Xml,
+            Format::Yaml,
+        ]
+    }
+
+    /// The value of the `format` parameter, e.g. `csv`.
+    pub fn as_str(&self) -> &'static str {
+        (*self).into()
+    }
+}
+
+/// A configured request without its API, e.g. to save it to disk and run it
+/// later. Created with `Request::to_spec`, turned back into a request with
+/// `HolidayAPI::load_request`.
+///
+/// The key is not part of it, it comes from the API the request is loaded
+/// with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestSpec {
+    /// Endpoint of the request, e.g. `holidays`.
+    pub endpoint: String,
+    pub parameters: BTreeMap<String, String>,
+    /// Set by `Request::timeout`, in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// Set by `Request::observed`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub by_observed: bool,
+    /// Set by `Request::header`, as `(name, value)` pairs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
+    /// Set by `Request::correlation_id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+}
+
+/// Parameters of a `Request`. Names and fixed values such as `"true"` are
+/// borrowed, so that building a request and cloning it along the builder
+/// chain does not allocate them over and over.
+pub(crate) type Parameters = HashMap<Cow<'static, str>, Cow<'static, str>>;
 
 #[derive(Debug, Clone)]
 pub struct Request<T: Clone> {
-    parameters: HashMap<String, String>,
+    parameters: Parameters,
     api: HolidayAPI,
+    options: RequestOptions,
+    by_observed: bool,
     _marker: PhantomData<T>,
 }
 
 impl<T> Request<T>
 where
-    T: Clone + DeserializeOwned,
+    T: ApiResponse,
 {
-    /// Response format (csv, json, php, tsv, yaml and xml). Defaults to JSON.
-    /// Only work with `request.get_raw()`
-    pub fn format(&mut self, format: &str) -> Self {
-        self.parameters.insert("format".into(), format.into());
+    fn with_api(api: &HolidayAPI) -> Self {
+        Self {
+            parameters: HashMap::new(),
+            api: api.clone(),
+            options: RequestOptions::default(),
+            by_observed: false,
+            _marker: PhantomData,
+        }
+    }
+
+    fn set(&mut self, name: &'static str, value: impl Into<Cow<'static, str>>) {
+        self.parameters.insert(Cow::Borrowed(name), value.into());
+    }
+
+    /// The parameters as sent, only allocated once the request is.
+    fn owned_parameters(parameters: Parameters) -> HashMap<String, String> {
+        parameters
+            .into_iter()
+            .map(|(name, value)| (name.into_owned(), value.into_owned()))
+            .collect()
+    }
+
+    /// Response format (csv, json, php, tsv, yaml and xml), see `Format`.
+    /// Defaults to JSON.
+    ///
+    /// Only JSON responses can be parsed, other formats only work with
+    /// `request.get_raw()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// let request = api.holidays("us", 2020).format(Format::Csv);
+    /// let request = api.holidays("us", 2020).format("csv");
+    /// ```
+    pub fn format(&mut self, format: impl AsRef<str>) -> Self {
+        self.set("format", format.as_ref().to_owned());
+        self.to_owned()
+    }
+
+    /// Prettifies results to be more human-readable with `true`, or keeps
+    /// them compact with `false`, the default, which sends no `pretty`
+    /// parameter at all.
+    ///
+    /// Pretty printing only makes responses larger, it is meant for reading
+    /// `get_raw` output.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// let request = api.holidays("us", 2020).pretty(true);
+    /// ```
+    pub fn pretty(&mut self, pretty: bool) -> Self {
+        match pretty {
+            true => self.set("pretty", "true"),
+            false => {
+                self.parameters.remove("pretty");
+            }
+        }
+        self.to_owned()
+    }
+
+    /// Keeps results compact, same as `pretty(false)`. Responses are
+    /// compact unless `pretty(true)` is set, this only makes it explicit.
+    pub fn compact(&mut self) -> Self {
+        self.pretty(false)
+    }
+
+    /// Returns the settings of this request, without the API and its key.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// let spec = api.holidays("us", 2020).public(true).to_spec();
+    /// let saved = serde_json::to_string(&spec).unwrap();
+    ///
+    /// let spec: RequestSpec = serde_json::from_str(&saved).unwrap();
+    /// let request: Request<HolidaysResponse> = api.load_request(spec).unwrap();
+    /// ```
+    pub fn to_spec(&self) -> RequestSpec {
+        RequestSpec {
+            endpoint: T::ENDPOINT.into(),
+            parameters: Self::owned_parameters(self.parameters.clone())
+                .into_iter()
+                .collect(),
+            timeout_ms: self
+                .options
+                .timeout
+                .map(|timeout| timeout.as_millis() as u64),
+            by_observed: self.by_observed,
+            headers: self.options.headers.clone(),
+            correlation_id: self.options.correlation_id.clone(),
+        }
+    }
+
+    pub(crate) fn from_spec(api: &HolidayAPI, spec: RequestSpec) -> Result<Self, HolidayAPIError> {
+        if !spec.endpoint.eq_ignore_ascii_case(T::ENDPOINT) {
+            return Err(HolidayAPIError::InvalidRequest(format!(
+                "Cannot load a {} request as a {} one",
+                spec.endpoint,
+                T::ENDPOINT
+            )));
+        }
+        let mut request = Self::with_api(api);
+        request.parameters = spec
+            .parameters
+            .into_iter()
+            .map(|(name, value)| (name.into(), value.into()))
+            .collect();
+        request.options.timeout = spec.timeout_ms.map(Duration::from_millis);
+        request.by_observed = spec.by_observed;
+        request.options.headers = spec.headers;
+        request.options.correlation_id = spec.correlation_id;
+        Ok(request)
+    }
+
+    /// Time limit for this request, from sending it until its response has
+    /// been received. Takes precedence over `HolidayAPIBuilder::timeout`.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// let request = api.holidays("us", 2020).timeout(Duration::from_secs(60));
+    /// ```
+    pub fn timeout(&mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self.to_owned()
+    }
+
+    /// Identifies this request in the logs, e.g. with the ID of the job or
+    /// request of your own system it is made for.
+    ///
+    /// With the `tracing` feature, the ID is recorded as the `correlation_id`
+    /// of the `holidayapi.request` span, which the log lines of the request
+    /// and its retries share. Without one, an ID unique within the process is
+    /// generated for each request. The ID is not sent to the API, add it as a
+    /// `header` to forward it too.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// let request = api
+    ///     .holidays("us", 2020)
+    ///     .correlation_id("job-42")
+    ///     .header("X-Request-Id", "job-42");
+    /// ```
+    pub fn correlation_id(&mut self, id: &str) -> Self {
+        self.options.correlation_id = Some(id.to_owned());
         self.to_owned()
     }
 
-    /// Prettifies results to be more human-readable.
-    pub fn pretty(&mut self) -> Self {
-        self.parameters.insert("pretty".into(), "true".into());
+    /// Sends a header with this request only, e.g. an idempotency key or a
+    /// correlation ID, replacing any header of the same name set before.
+    ///
+    /// Headers are not part of the request's identity: the cache and
+    /// singleflight still serve a response to the same parameters fetched
+    /// with other headers. An invalid name or value is reported by `validate`.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// let request = api
+    ///     .holidays("us", 2020)
+    ///     .header("Idempotency-Key", "b0b7c4e4-8f5e-4a5e-9b43-2b0d3c1f2a6e");
+    /// ```
+    pub fn header(&mut self, name: &str, value: &str) -> Self {
+        let headers = &mut self.options.headers;
+        headers.retain(|(other, _)| !other.eq_ignore_ascii_case(name));
+        headers.push((name.to_owned(), value.to_owned()));
         self.to_owned()
     }
 
-    /// Return the raw String of the response
+    /// Checks the constraints between parameters that the API would reject,
+    /// e.g. `day` without `month` or both `previous` and `upcoming`, as well
+    /// as the range of numbers, the format of dates and the extra headers.
+    ///
+    /// Called before sending the request by `get_raw`, `get_full`, `stream`
+    /// and the methods built on them, which return the error as
+    /// `HolidayAPIError::Validation`.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// let request = api.holidays("US", 2020).day(4);
+    /// assert_eq!(request.validate(), Err(ValidationError::MissingMonth("day")));
+    /// ```
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        for (name, value) in &self.options.headers {
+            validation::validate_header(name, value)?;
+        }
+        validation::validate(&self.parameters)
+    }
+
+    /// Return the raw String of the response, from the endpoint of the
+    /// request.
+    ///
+    /// The body is in the format set with `format`, sent as is, or in JSON
+    /// when none is set: `format=json` is always sent explicitly rather than
+    /// relying on the API's default.
     pub async fn get_raw(self) -> Result<String, HolidayAPIError> {
-        Ok(self
-            .api
-            .custom_request("countries", self.parameters)
-            .await?
-            .text()
-            .await
-            .unwrap())
+        self.validate()?;
+        let mut param = self.parameters;
+        param
+            .entry("format".into())
+            .or_insert(Cow::Borrowed(Format::Json.as_ref()));
+        let param = Self::owned_parameters(param);
+        self.api.fetch_body(T::ENDPOINT, param, &self.options).await
+    }
+
+    /// Like `get_raw`, but returns the headers of the response along with
+    /// its body, e.g. to look at rate limits, caching headers or an ETag.
+    ///
+    /// Always sends the request: the cache and singleflight are bypassed, as
+    /// they only keep bodies. The headers are returned as received, nothing is
+    /// redacted, so mind that they may contain sensitive information such as
+    /// cookies before logging them.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn etag(api: HolidayAPI) -> Result<Option<String>, HolidayAPIError> {
+    ///     let (headers, _body) = api.countries().get_raw_with_headers().await?;
+    ///     Ok(headers
+    ///         .get("etag")
+    ///         .and_then(|etag| etag.to_str().ok())
+    ///         .map(str::to_owned))
+    /// }
+    /// ```
+    pub async fn get_raw_with_headers(self) -> Result<(HeaderMap, String), HolidayAPIError> {
+        let api = self.api.clone();
+        let response = self.send().await?;
+        let headers = response.headers().clone();
+        let body = api.read_body(response).await?;
+        Ok((headers, body))
+    }
+
+    /// Like `get_raw`, but returns the body as bytes, e.g. to forward it
+    /// as is, without requiring it to be valid UTF-8.
+    ///
+    /// Always sends the request, as the cache and singleflight only keep
+    /// text bodies.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn csv(api: HolidayAPI) -> Result<Vec<u8>, HolidayAPIError> {
+    ///     api.holidays("us", 2020).format(Format::Csv).get_bytes().await
+    /// }
+    /// ```
+    pub async fn get_bytes(self) -> Result<Vec<u8>, HolidayAPIError> {
+        let api = self.api.clone();
+        let response = self.send().await?;
+        api.read_bytes(response).await
+    }
+
+    /// Sends the request and returns the response without reading its body,
+    /// e.g. to inspect headers, decode it yourself or stream it, below
+    /// `get_raw` and `get_full`.
+    ///
+    /// The request is validated, retried and traced like any other, but skips
+    /// the cache and singleflight. Error statuses and a content type that does
+    /// not match the format are still returned as errors. Reading the body,
+    /// and enforcing a size limit on it, is up to the caller. Keep in mind
+    /// that `Response::url` includes the key, so do not log it.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn etag(api: HolidayAPI) -> Result<Option<String>, HolidayAPIError> {
+    ///     let response = api.countries().get_response().await?;
+    ///     Ok(response
+    ///         .headers()
+    ///         .get("etag")
+    ///         .and_then(|etag| etag.to_str().ok())
+    ///         .map(str::to_owned))
+    /// }
+    /// ```
+    pub async fn get_response(self) -> Result<Response, HolidayAPIError> {
+        self.send().await
+    }
+
+    /// Sends the request as `get_raw` does, bypassing the cache and
+    /// singleflight, and returns the response once its headers are checked.
+    async fn send(self) -> Result<Response, HolidayAPIError> {
+        self.validate()?;
+        let mut param = self.parameters;
+        let format = param
+            .entry("format".into())
+            .or_insert(Cow::Borrowed(Format::Json.as_ref()))
+            .to_string();
+        let param = Self::owned_parameters(param);
+        let response = self.api.request(T::ENDPOINT, param, &self.options).await?;
+        HolidayAPI::check_content_type(&response, Some(&format))?;
+        Ok(response)
     }
 
     /// Returns the parsed struct of the response if successful
+    ///
+    /// Fails with `HolidayAPIError::Warning` if the response has a warning
+    /// and `HolidayAPIBuilder::strict_warnings` is enabled.
     pub async fn get_full(self) -> Result<T, HolidayAPIError> {
-        let mut param = self.parameters;
-        param.insert("format".into(), "json".into());
-        Ok(serde_json::from_str(
-            self.api
-                .custom_request("countries", param)
-                .await?
-                .text()
-                .await
-                .unwrap()
-                .as_str(),
-        )
-        .expect("JSON is valid"))
+        Ok(self.get_full_with_raw().await?.0)
+    }
+
+    /// Returns the parsed struct of the response along with its raw body,
+    /// e.g. to log the payload with the typed data, from a single request.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn logged(api: HolidayAPI) -> Result<Vec<Holiday>, HolidayAPIError> {
+    ///     let (response, raw) = api.holidays("us", 2020).get_full_with_raw().await?;
+    ///     println!("{}", raw);
+    ///     Ok(response.holidays)
+    /// }
+    /// ```
+    pub async fn get_full_with_raw(self) -> Result<(T, String), HolidayAPIError> {
+        let strict = self.api.strict_warnings;
+        let body = self.get_json().await?;
+        let response: T =
+            serde_json::from_str(&body).map_err(|e| HolidayAPIError::parse(e, &body))?;
+        match response.api_warning() {
+            Some(warning) if strict => Err(HolidayAPIError::Warning(warning)),
+            _ => Ok((response, body)),
+        }
+    }
+
+    /// Returns the response parsed into a type of your choice, e.g. a leaner
+    /// struct with only the fields you need.
+    ///
+    /// You are responsible for `U` matching the JSON shape of the endpoint's
+    /// response, see the bundled response structs for reference.
+    ///
+    /// Requests JSON unless a format has been set with `format`. Any other
+    /// format fails with `HolidayAPIError::UnsupportedFormat` before anything
+    /// is sent.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Names {
+    ///     holidays: Vec<Name>,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Name {
+    ///     name: String,
+    /// }
+    ///
+    /// async fn names(api: HolidayAPI) -> Result<Vec<String>, HolidayAPIError> {
+    ///     let names: Names = api.holidays("us", 2020).get_as().await?;
+    ///     Ok(names.holidays.into_iter().map(|h| h.name).collect())
+    /// }
+    /// ```
+    pub async fn get_as<U: DeserializeOwned>(self) -> Result<U, HolidayAPIError> {
+        let body = self.get_json().await?;
+        serde_json::from_str(&body).map_err(|e| HolidayAPIError::parse(e, &body))
+    }
+
+    async fn get_json(self) -> Result<String, HolidayAPIError> {
+        self.validate()?;
+        let param = Self::json_parameters(self.parameters)?;
+        self.api.fetch_body(T::ENDPOINT, param, &self.options).await
+    }
+
+    /// Requests JSON unless another format has been set, which cannot be parsed.
+    fn json_parameters(
+        mut parameters: Parameters,
+    ) -> Result<HashMap<String, String>, HolidayAPIError> {
+        match parameters.get("format") {
+            None => {
+                parameters.insert("format".into(), Cow::Borrowed(Format::Json.as_ref()));
+            }
+            Some(format) if Format::from_str(format) == Ok(Format::Json) => {}
+            Some(format) => return Err(HolidayAPIError::UnsupportedFormat(format.to_string())),
+        }
+        Ok(Self::owned_parameters(parameters))
     }
 }
 
 impl Request<CountriesResponse> {
     pub(crate) fn new(api: &HolidayAPI) -> Self {
-        Self {
-            parameters: HashMap::new(),
-            api: api.clone(),
-            _marker: PhantomData,
-        }
+        Self::with_api(api)
     }
 
     /// Return only the country with the specified code.
@@ -80,7 +522,7 @@ impl Request<CountriesResponse> {
     /// let request = api.countries().country("us");
     /// ```
     pub fn country(&mut self, country: &str) -> Self {
-        self.parameters.insert("country".into(), country.into());
+        self.set("country", country.to_owned());
         self.to_owned()
     }
 
@@ -94,40 +536,83 @@ impl Request<CountriesResponse> {
     /// let request = api.countries().search("Japan");
     /// ```
     pub fn search(&mut self, search: &str) -> Self {
-        self.parameters.insert("search".into(), search.into());
+        self.set("search", search.to_owned());
         self.to_owned()
     }
 
-    /// Return only countries that have public holidays.
+    /// Return only countries that have public holidays with `true`, or every
+    /// country with `false`, the default, which sends no `public` parameter.
     ///
     /// # Examples
     /// ```
     /// use holidayapi_rust::prelude::*;
     ///
     /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
-    /// let request = api.countries().public();
+    /// let request = api.countries().public(true);
     /// ```
-    pub fn public(&mut self) -> Self {
-        self.parameters.insert("public".into(), "true".to_string());
+    pub fn public(&mut self, public: bool) -> Self {
+        match public {
+            true => self.set("public", "true"),
+            false => {
+                self.parameters.remove("public");
+            }
+        }
         self.to_owned()
     }
 
-    /// Returns only the important `Vec<Holiday>` field.
+    /// Returns only the important `Vec<Country>` field.
+    ///
+    /// Country and subdivision names are always in English: unlike the
+    /// holidays endpoint, the countries endpoint has no `language` parameter.
+    /// `Country::languages` lists the languages spoken in each country instead.
     pub async fn get(self) -> Result<Vec<Country>, HolidayAPIError> {
         Ok(self.get_full().await?.countries)
     }
+
+    /// Returns only the `(code, name)` of the matching countries, e.g. for an
+    /// autocomplete. Skips parsing the rest of each country.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn suggest(api: HolidayAPI, query: &str) -> Result<Vec<(String, String)>, HolidayAPIError> {
+    ///     api.countries().search(query).codes_only().await
+    /// }
+    /// ```
+    pub async fn codes_only(self) -> Result<Vec<(String, String)>, HolidayAPIError> {
+        #[derive(Deserialize)]
+        struct Codes {
+            #[serde(default)]
+            countries: Option<Vec<Code>>,
+        }
+        #[derive(Deserialize)]
+        struct Code {
+            code: String,
+            name: String,
+        }
+
+        let codes: Codes = self.get_as().await?;
+        Ok(codes
+            .countries
+            .unwrap_or_default()
+            .into_iter()
+            .map(|country| (country.code, country.name))
+            .collect())
+    }
 }
 
 impl Request<HolidaysResponse> {
+    /// Most pages requested by `get` for a paginated response.
+    pub const MAX_PAGES: usize = 100;
+    /// Fewest characters the API accepts for a `search`.
+    pub const SEARCH_MIN_CHARS: usize = 5;
+
     pub(crate) fn new(api: &HolidayAPI, country: String, year: i32) -> Self {
-        let mut holiday = Self {
-            parameters: HashMap::new(),
-            api: api.clone(),
-            _marker: PhantomData,
-        };
-        holiday.parameters.insert("country".into(), country);
-        holiday.parameters.insert("year".into(), year.to_string());
-        return holiday;
+        let mut holiday = Self::with_api(api);
+        holiday.set("country", country);
+        holiday.set("year", year.to_string());
+        holiday
     }
 
     /// 1 or 2 digit month (1-12).
@@ -140,7 +625,7 @@ impl Request<HolidaysResponse> {
     /// let request = api.holidays("JP", 2020).month(12);
     /// ```
     pub fn month(&mut self, month: i32) -> Self {
-        self.parameters.insert("month".into(), month.to_string());
+        self.set("month", month.to_string());
         self.to_owned()
     }
 
@@ -154,23 +639,62 @@ impl Request<HolidaysResponse> {
     /// let request = api.holidays("JP", 2020).month(12).day(20);
     /// ```
     pub fn day(&mut self, day: i32) -> Self {
-        self.parameters.insert("day".into(), day.to_string());
+        self.set("day", day.to_string());
         self.to_owned()
     }
 
-    /// Return only public holidays.
-    pub fn public(&mut self) -> Self {
-        self.parameters.insert("public".into(), "true".into());
+    /// Return only public holidays with `true`, or every holiday with
+    /// `false`, the default, which sends no `public` parameter at all. The
+    /// API cannot return non-public holidays only, filter on
+    /// `Holiday::public` for those.
+    ///
+    /// The only filter on the kind of holiday: the API has no categories
+    /// beyond `Holiday::public`.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// let public_only = true;
+    /// let request = api.holidays("us", 2020).public(public_only);
+    /// ```
+    pub fn public(&mut self, public: bool) -> Self {
+        match public {
+            true => self.set("public", "true"),
+            false => {
+                self.parameters.remove("public");
+            }
+        }
         self.to_owned()
     }
 
     /// Return state / province holidays alongside countrywide holidays.
+    ///
+    /// A country without subdivisions just gets its national holidays, with
+    /// an empty `Holiday::subdivisions`. Should the API reject the request
+    /// with a `400 Bad Request` instead, `get` says subdivisions were
+    /// requested in the error message.
     pub fn subdivisions(&mut self) -> Self {
-        self.parameters.insert("subdivisions".into(), "true".into());
+        self.set("subdivisions", "true");
         self.to_owned()
     }
 
-    /// Search holidays by name. Minimum 5 characters.
+    /// Return only public holidays, state / province ones included. Sets both
+    /// `public=true` and `subdivisions=true`, same as `.public(true).subdivisions()`.
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    ///
+    /// let request = api.holidays("US", 2020).regional_public();
+    /// ```
+    pub fn regional_public(&mut self) -> Self {
+        self.public(true);
+        self.subdivisions()
+    }
+
+    /// Search holidays by name. Minimum `SEARCH_MIN_CHARS` characters.
     /// # Examples
     /// ```
     /// use holidayapi_rust::prelude::*;
@@ -179,12 +703,13 @@ impl Request<HolidaysResponse> {
     /// let request = api.holidays("JP", 2020).search("independence day");
     /// ```
     pub fn search(&mut self, search: &str) -> Self {
-        self.parameters.insert("search".into(), search.to_string());
+        self.set("search", search.to_string());
         self.to_owned()
     }
 
     /// ISO 639-1 format (with exceptions).
     /// Click [here](https://holidayapi.com/languages) for supported languages.
+    /// Takes a `&str` or a `LanguageCode` to catch typos early.
     ///
     /// # Examples
     /// ```
@@ -193,9 +718,8 @@ impl Request<HolidaysResponse> {
     ///
     /// let request = api.holidays("JP", 2020).language("en");
     /// ```
-    pub fn language(&mut self, language: &str) -> Self {
-        self.parameters
-            .insert("language".into(), language.to_string());
+    pub fn language(&mut self, language: impl AsRef<str>) -> Self {
+        self.set("language", language.as_ref().to_owned());
         self.to_owned()
     }
 
@@ -203,7 +727,7 @@ impl Request<HolidaysResponse> {
     ///
     /// Cannot be used with `upcoming`.
     pub fn previous(&mut self) -> Self {
-        self.parameters.insert("previous".into(), "true".into());
+        self.set("previous", "true");
         self.to_owned()
     }
 
@@ -211,29 +735,244 @@ impl Request<HolidaysResponse> {
     ///
     /// Cannot be used with previous.
     pub fn upcoming(&mut self) -> Self {
-        self.parameters.insert("upcoming".into(), "true".into());
+        self.set("upcoming", "true");
+        self.to_owned()
+    }
+
+    /// Order the holidays returned by `get` by their observed date instead of
+    /// their actual date.
+    ///
+    /// The API always returns both dates, this only changes the client-side
+    /// ordering of the result. Holidays sharing an observed date keep their
+    /// original order.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    ///
+    /// let request = api.holidays("US", 2020).observed();
+    /// ```
+    pub fn observed(&mut self) -> Self {
+        self.by_observed = true;
         self.to_owned()
     }
 
     /// Returns only the important `Vec<Holiday>` field.
+    ///
+    /// The API returns every holiday at once today. Should a response ever
+    /// carry a `HolidaysResponse::pagination`, the following pages are
+    /// requested too, up to `MAX_PAGES` in total, and their holidays
+    /// appended in order.
+    ///
+    /// Fails with `HolidayAPIError::InvalidResponse` rather than returning a
+    /// truncated list if the last of those pages still points to a next page.
     pub async fn get(self) -> Result<Vec<Holiday>, HolidayAPIError> {
-        Ok(self.get_full().await?.holidays)
+        let by_observed = self.by_observed;
+        let mut holidays = Vec::new();
+        let mut page = self.clone();
+        let mut requested = 0;
+        loop {
+            if requested == Self::MAX_PAGES {
+                return Err(HolidayAPIError::InvalidResponse(format!(
+                    "More than {} pages of holidays",
+                    Self::MAX_PAGES
+                )));
+            }
+            requested += 1;
+            let response = page
+                .clone()
+                .get_full()
+                .await
+                .map_err(|err| self.explain_subdivisions(err))?;
+            holidays.extend(response.holidays);
+            match response.pagination {
+                Some(Pagination {
+                    next_page: Some(next),
+                    ..
+                }) => page.set("page", next.to_string()),
+                Some(Pagination {
+                    next_cursor: Some(next),
+                    ..
+                }) => page.set("cursor", next),
+                _ => break,
+            }
+        }
+        if by_observed {
+            holidays.sort_by(|a, b| a.observed.cmp(&b.observed));
+        }
+        Ok(holidays)
+    }
+
+    /// Adds to the message of a `400 Bad Request` that subdivisions were
+    /// requested, the likely cause for a country without any.
+    fn explain_subdivisions(&self, err: HolidayAPIError) -> HolidayAPIError {
+        match err {
+            HolidayAPIError::RequestError(err, message)
+                if self.parameters.contains_key("subdivisions")
+                    && err.status() == Some(reqwest::StatusCode::BAD_REQUEST) =>
+            {
+                let country = self.parameters.get("country").cloned().unwrap_or_default();
+                HolidayAPIError::RequestError(
+                    err,
+                    format!(
+                        "{} (subdivisions were requested for {}, which may not have any)",
+                        message, country
+                    ),
+                )
+            }
+            err => err,
+        }
+    }
+
+    /// Returns the holidays indexed by their `date`, or their observed date
+    /// when `observed` has been set, several holidays of a day in the order
+    /// of the response.
+    ///
+    /// Dates are formatted as `YYYY-MM-DD`, whose lexicographic order is the
+    /// chronological one, so iterating over the map goes from the first day
+    /// to the last.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn agenda(api: HolidayAPI) -> Result<(), HolidayAPIError> {
+    ///     for (date, holidays) in api.holidays("us", 2020).get_sorted_map().await? {
+    ///         println!("{}: {} holidays", date, holidays.len());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_sorted_map(self) -> Result<BTreeMap<String, Vec<Holiday>>, HolidayAPIError> {
+        let by_observed = self.by_observed;
+        let mut map: BTreeMap<String, Vec<Holiday>> = BTreeMap::new();
+        for holiday in self.get().await? {
+            let date = match by_observed {
+                true => holiday.observed.clone(),
+                false => holiday.date.clone(),
+            };
+            map.entry(date).or_default().push(holiday);
+        }
+        Ok(map)
+    }
+
+    /// Returns the holidays indexed by their `uuid`, e.g. to sync them with a
+    /// store that tracks holidays by it.
+    ///
+    /// Should the response hold several holidays with the same `uuid`, one is
+    /// kept as in `merge_holidays`: the subdivision entry if there is one,
+    /// otherwise the first.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn removed(api: HolidayAPI, known: &[String]) -> Result<usize, HolidayAPIError> {
+    ///     let holidays = api.holidays("us", 2020).get_by_uuid().await?;
+    ///     Ok(known.iter().filter(|uuid| !holidays.contains_key(*uuid)).count())
+    /// }
+    /// ```
+    pub async fn get_by_uuid(self) -> Result<HashMap<String, Holiday>, HolidayAPIError> {
+        let mut map: HashMap<String, Holiday> = HashMap::new();
+        for holiday in self.get().await? {
+            match map.entry(holiday.uuid.clone()) {
+                Entry::Occupied(mut entry) => {
+                    if holiday.is_regional() && !entry.get().is_regional() {
+                        entry.insert(holiday);
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(holiday);
+                }
+            }
+        }
+        Ok(map)
+    }
+
+    /// Returns the number of holidays matching the request.
+    ///
+    /// The API has no count endpoint, so this still fetches the full list.
+    pub async fn count(self) -> Result<usize, HolidayAPIError> {
+        Ok(self.get().await?.len())
+    }
+
+    /// Yields each `Holiday` as soon as it has been read from the response body,
+    /// instead of buffering the whole response first.
+    ///
+    /// Useful for very large responses. Errors from the request itself are
+    /// returned up front, while malformed items or a broken connection are
+    /// yielded by the stream.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures_util::StreamExt;
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn print_holidays(api: HolidayAPI) -> Result<(), HolidayAPIError> {
+    ///     let mut holidays = Box::pin(api.holidays("us", 2020).stream().await?);
+    ///     while let Some(holiday) = holidays.next().await {
+    ///         println!("{}", holiday?.name);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn stream(
+        self,
+    ) -> Result<impl Stream<Item = Result<Holiday, HolidayAPIError>>, HolidayAPIError> {
+        self.validate()?;
+        let param = Self::json_parameters(self.parameters)?;
+        let response = self
+            .api
+            .request(HolidaysResponse::ENDPOINT, param, &self.options)
+            .await?;
+        HolidayAPI::check_content_type(&response, Some("json"))?;
+        Ok(stream::holidays(response, self.api.max_response_bytes))
     }
 }
 
 impl Request<WorkdayResponse> {
     pub(crate) fn new(api: &HolidayAPI, country: &str, start: &str, days: i32) -> Self {
-        let mut workday = Self {
-            parameters: HashMap::new(),
-            api: api.clone(),
-            _marker: PhantomData,
-        };
+        let mut workday = Self::with_api(api);
+        workday.set("country", country.to_string());
+        workday.set("start", start.to_string());
+        workday.set("days", days.to_string());
         workday
-            .parameters
-            .insert("country".into(), country.to_string());
-        workday.parameters.insert("start".into(), start.to_string());
-        workday.parameters.insert("days".into(), days.to_string());
-        return workday;
+    }
+
+    /// Replaces the start date with a typed one, formatted as `YYYY-MM-DD`.
+    ///
+    /// # Examples
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// let start = NaiveDate::from_ymd_opt(2020, 7, 1).unwrap();
+    /// let request = api.workday("US", "", 4).start_date(start);
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn start_date(&mut self, start: NaiveDate) -> Self {
+        self.set("start", start.format("%Y-%m-%d").to_string());
+        self.to_owned()
+    }
+
+    /// Counts `days` workdays backward from the start date instead, e.g. for
+    /// the last day to file something `days` business days before a
+    /// deadline. Replaces the `days` of `HolidayAPI::workday`, sent as a
+    /// negative number, which the API counts backward.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// // Same as api.workday("US", "2020-07-31", -10).
+    /// let request = api.workday("US", "2020-07-31", 0).days_before(10);
+    /// ```
+    pub fn days_before(&mut self, days: u32) -> Self {
+        self.set("days", (-i64::from(days)).to_string());
+        self.to_owned()
     }
 
     /// Returns only the important `("YYYY-MM-DD", Weekday)` tuple.
@@ -245,19 +984,38 @@ impl Request<WorkdayResponse> {
 
 impl Request<WorkdaysResponse> {
     pub fn new(api: &HolidayAPI, country: &str, start: &str, days: &str) -> Self {
-        let mut workdays = Self {
-            parameters: HashMap::new(),
-            api: api.clone(),
-            _marker: PhantomData,
-        };
-        workdays
-            .parameters
-            .insert("country".into(), country.to_string());
+        let mut workdays = Self::with_api(api);
+        workdays.set("country", country.to_string());
+        workdays.set("start", start.to_string());
+        workdays.set("end", days.to_string());
         workdays
-            .parameters
-            .insert("start".into(), start.to_string());
-        workdays.parameters.insert("end".into(), days.to_string());
-        return workdays;
+    }
+
+    /// Replaces the start date with a typed one, formatted as `YYYY-MM-DD`.
+    ///
+    /// # Examples
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
+    /// let date = |month, day| NaiveDate::from_ymd_opt(2020, month, day).unwrap();
+    /// let request = api
+    ///     .workdays("US", "", "")
+    ///     .start_date(date(7, 1))
+    ///     .end_date(date(7, 31));
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn start_date(&mut self, start: NaiveDate) -> Self {
+        self.set("start", start.format("%Y-%m-%d").to_string());
+        self.to_owned()
+    }
+
+    /// Replaces the end date with a typed one, formatted as `YYYY-MM-DD`.
+    #[cfg(feature = "chrono")]
+    pub fn end_date(&mut self, end: NaiveDate) -> Self {
+        self.set("end", end.format("%Y-%m-%d").to_string());
+        self.to_owned()
     }
 
     /// Returns the number of working / business days between the specified start and end dates.
@@ -265,18 +1023,88 @@ impl Request<WorkdaysResponse> {
         let res = self.get_full().await?;
         Ok(res.workdays)
     }
+
+    /// Returns the public holidays skipped by the count of `get`, i.e. those
+    /// observed on a weekday between the start and end dates, in order of
+    /// their observed date. Several holidays may share a day.
+    ///
+    /// The endpoint only returns the count, so this fetches the public
+    /// holidays of every year between the dates instead, one request per
+    /// year, and does not send the `workdays` request itself. The previous
+    /// or next year is fetched too when the range starts in January or ends
+    /// in December, as a holiday can be observed in another year than its
+    /// date.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn explain(api: HolidayAPI) -> Result<(), HolidayAPIError> {
+    ///     let request = api.workdays("US", "2020-07-01", "2020-07-31");
+    ///     for holiday in request.clone().skipped_holidays().await? {
+    ///         println!("{} is off for {}", holiday.observed, holiday.name);
+    ///     }
+    ///     println!("{} workdays", request.get().await?);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn skipped_holidays(self) -> Result<Vec<Holiday>, HolidayAPIError> {
+        self.validate()?;
+        let get = |name: &str| self.parameters.get(name).map(|value| value.as_ref());
+        let (Some(country), Some(start), Some(end)) = (get("country"), get("start"), get("end"))
+        else {
+            return Err(HolidayAPIError::InvalidRequest(
+                "A workdays request needs a country, a start and an end".into(),
+            ));
+        };
+        let (start, end) = if start <= end {
+            (start, end)
+        } else {
+            (end, start)
+        };
+        // Dates are validated as YYYY-MM-DD, so they compare as strings.
+        let year = |date: &str| {
+            date.get(..4)
+                .and_then(|year| year.parse::<i32>().ok())
+                .ok_or_else(|| HolidayAPIError::InvalidRequest(format!("Invalid date: {}", date)))
+        };
+        // A holiday can be observed in another year than its date, e.g. a New
+        // Year's Day on a Saturday observed on December 31st.
+        let (mut first, mut last) = (year(start)?, year(end)?);
+        if start.get(4..8) == Some("-01-") {
+            first -= 1;
+        }
+        if end.get(4..8) == Some("-12-") {
+            last += 1;
+        }
+
+        let mut skipped: Vec<Holiday> = Vec::new();
+        for year in first..=last {
+            let holidays = self.api.holidays(country, year).public(true).get().await?;
+            for holiday in holidays {
+                let observed = holiday.observed.as_str();
+                if holiday.public
+                    && (start..=end).contains(&observed)
+                    && holiday.is_observed_on_weekday()
+                    // Listed once, even if a neighbouring year returned it too.
+                    && !skipped.iter().any(|skip| skip.uuid == holiday.uuid)
+                {
+                    skipped.push(holiday);
+                }
+            }
+        }
+        skipped.sort_by(|a, b| a.observed.cmp(&b.observed));
+        Ok(skipped)
+    }
 }
 
 impl Request<LanguagesResponse> {
     pub fn new(api: &HolidayAPI) -> Self {
-        Self {
-            parameters: HashMap::new(),
-            api: api.clone(),
-            _marker: PhantomData,
-        }
+        Self::with_api(api)
     }
 
-    /// Return only the language with the specified code.
+    /// Return only the language with the specified code, a `&str` or a
+    /// `LanguageCode`.
     /// # Examples
     /// ```
     /// use holidayapi_rust::prelude::*;
@@ -284,8 +1112,8 @@ impl Request<LanguagesResponse> {
     /// let api = HolidayAPI::new("00000000-0000-0000-0000-000000000000").unwrap();
     /// let request = api.languages().language("us");
     /// ```
-    pub fn language(&mut self, language: &str) -> Self {
-        self.parameters.insert("language".into(), language.into());
+    pub fn language(&mut self, language: impl AsRef<str>) -> Self {
+        self.set("language", language.as_ref().to_owned());
         self.to_owned()
     }
 
@@ -298,7 +1126,7 @@ impl Request<LanguagesResponse> {
     /// let request = api.languages().search("Japan");
     /// ```
     pub fn search(&mut self, search: &str) -> Self {
-        self.parameters.insert("search".into(), search.into());
+        self.set("search", search.to_owned());
         self.to_owned()
     }
 
@@ -1,32 +1,182 @@
-use serde::Deserialize;
+use crate::country::CountryCode;
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Utc};
+use regex::Regex;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use std::sync::OnceLock;
+
+/// A response body returned by one of the API endpoints.
+pub trait ApiResponse: DeserializeOwned + Clone {
+    /// Endpoint path relative to the versioned base url, e.g. `holidays`.
+    const ENDPOINT: &'static str;
+
+    /// The `warning` of the response, if any, parsed with `ApiWarning::parse`.
+    fn api_warning(&self) -> Option<ApiWarning> {
+        None
+    }
+
+    /// JSON Schema of the response, e.g. to generate types for another
+    /// language or to validate payloads.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// let schema = serde_json::to_value(HolidaysResponse::json_schema()).unwrap();
+    /// assert_eq!(schema["title"], "HolidaysResponse");
+    /// ```
+    #[cfg(feature = "schemars")]
+    fn json_schema() -> schemars::schema::RootSchema
+    where
+        Self: schemars::JsonSchema,
+    {
+        schemars::schema_for!(Self)
+    }
+}
+
+/// A `warning` returned by the API alongside a successful response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiWarning {
+    /// A parameter of the request is deprecated.
+    Deprecation(Deprecation),
+    /// Any other warning, as is.
+    Other(String),
+}
+
+/// A deprecated parameter, reported in the `warning` of a response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deprecation {
+    /// Name of the parameter, e.g. `country`.
+    pub parameter: String,
+    /// The whole warning, which may name a replacement.
+    pub message: String,
+}
+
+impl ApiWarning {
+    /// Recognizes warnings such as ``The `country` parameter is deprecated``
+    /// or `Parameter 'year' has been deprecated` as a `Deprecation`, and keeps
+    /// any other warning as `Other`.
+    ///
+    /// The name must be quoted or next to the word `parameter`, so that e.g.
+    /// `This endpoint is deprecated` is not taken for a parameter.
+    pub fn parse(warning: &str) -> ApiWarning {
+        static DEPRECATION: OnceLock<Regex> = OnceLock::new();
+        let deprecation = DEPRECATION.get_or_init(|| {
+            Regex::new(
+                r#"(?i)(?:parameter\s+[`'"]?(\w+)[`'"]?|[`'"](\w+)[`'"](?:\s+parameter)?|(\w+)\s+parameter)\s+(?:is|has\s+been|was)\s+deprecated"#,
+            )
+            .expect("Regex is correct")
+        });
+
+        let parameter = deprecation
+            .captures(warning)
+            .and_then(|captures| captures.iter().skip(1).flatten().next())
+            .map(|parameter| parameter.as_str().to_owned());
+        match parameter {
+            Some(parameter) => ApiWarning::Deprecation(Deprecation {
+                parameter,
+                message: warning.to_owned(),
+            }),
+            None => ApiWarning::Other(warning.to_owned()),
+        }
+    }
+}
+
+/// Deserializes a `null` value as the type's default, e.g. an empty `Vec`.
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
 
 #[derive(Debug, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct APIRequests {
     pub available: u32,
     pub used: u32,
+    /// When the quota resets, formatted as `2019-10-01 00:00:00` in UTC.
     pub resets: String,
 }
 
+#[cfg(feature = "chrono")]
+impl APIRequests {
+    /// Parses `resets`, e.g. to wait until the quota resets.
+    ///
+    /// The API reports it as `YYYY-MM-DD HH:MM:SS` in UTC. RFC 3339 timestamps
+    /// are accepted too.
+    pub fn resets_at(&self) -> Result<DateTime<Utc>, chrono::ParseError> {
+        NaiveDateTime::parse_from_str(&self.resets, "%Y-%m-%d %H:%M:%S")
+            .map(|resets| resets.and_utc())
+            .or_else(|_| DateTime::parse_from_rfc3339(&self.resets).map(|resets| resets.to_utc()))
+    }
+}
+
+/// Body of an error response, shared by every endpoint.
+#[derive(Debug, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ApiError {
+    pub status: u32,
+    pub error: String,
+}
+
 #[derive(Debug, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CountriesResponse {
     pub requests: APIRequests,
     pub status: u32,
     pub error: Option<String>,
     pub warning: Option<String>,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub countries: Vec<Country>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Country {
     pub code: String,
+    /// English name, the countries endpoint cannot localize it.
     pub name: String,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub languages: Vec<String>,
     pub codes: Codes,
+    /// Flag of the country as returned by the API, currently the URL of a
+    /// PNG image such as `https://holidayapi.com/flags/us.png`. Prefer
+    /// `flag_url` and `flag_emoji`, which also handle an emoji here.
     pub flag: String,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub subdivisions: Vec<Subdivision>,
 }
 
+impl Country {
+    /// URL of the flag image, `None` when `flag` is not an http(s) URL.
+    pub fn flag_url(&self) -> Option<&str> {
+        let flag = self.flag.trim();
+        let scheme = flag.get(..8)?.to_ascii_lowercase();
+        (scheme.starts_with("http://") || scheme == "https://").then_some(flag)
+    }
+
+    /// The flag as an emoji, e.g. 🇯🇵 for `JP`: `flag` itself when it is not
+    /// a URL, otherwise built from the alpha-2 code. `None` if neither is
+    /// usable.
+    pub fn flag_emoji(&self) -> Option<String> {
+        let flag = self.flag.trim();
+        if !flag.is_empty() && self.flag_url().is_none() {
+            return Some(flag.to_owned());
+        }
+        let code = &self.codes.alpha_2;
+        if code.len() != 2 || !code.bytes().all(|b| b.is_ascii_alphabetic()) {
+            return None;
+        }
+        code.bytes()
+            .map(|b| char::from_u32(0x1F1E6 + u32::from(b.to_ascii_uppercase() - b'A')))
+            .collect()
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Codes {
     #[serde(rename = "alpha-2")]
     pub alpha_2: String,
@@ -36,45 +186,197 @@ pub struct Codes {
 }
 
 #[derive(Debug, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Subdivision {
     pub code: String,
+    /// Name as returned by the API, in English like `Country::name`. Any
+    /// UTF-8 name is kept as is, e.g. one with accents.
     pub name: String,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub languages: Vec<String>,
 }
 
+/// Deserializes a value as `None` rather than failing when it does not have
+/// the expected shape.
+fn lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(serde_json::from_value(value).ok())
+}
+
+/// A holidays response is currently one page holding every holiday of the
+/// request, without a `pagination` field.
 #[derive(Debug, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct HolidaysResponse {
     pub requests: APIRequests,
     pub status: u32,
+    /// Empty when no holiday matches the request.
+    #[serde(default, deserialize_with = "null_as_default")]
     pub holidays: Vec<Holiday>,
     pub error: Option<String>,
     pub warning: Option<String>,
+    /// Where the rest of the holidays are, should the API ever split large
+    /// responses into pages. `None` today, and when the field does not have
+    /// the expected shape.
+    #[serde(default, deserialize_with = "lenient")]
+    pub pagination: Option<Pagination>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+/// How to request the next page of a paginated response, by page number or
+/// by cursor. Not returned by the API today.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Pagination {
+    /// Number of the next page, sent as the `page` parameter.
+    #[serde(default)]
+    pub next_page: Option<u32>,
+    /// Cursor of the next page, sent as the `cursor` parameter.
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Holiday {
     pub name: String,
     pub date: String,
     pub observed: String,
+    /// Whether the holiday is a public one, i.e. a day off. This is the only
+    /// kind the API tells apart: it has no category such as bank, optional
+    /// or religious holidays, so those cannot be distinguished.
     pub public: bool,
     pub country: String,
     pub uuid: String,
     pub weekday: Weekday,
+    /// Subdivision codes (e.g. `US-CA`) the holiday is limited to. Empty for
+    /// countrywide holidays.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub subdivisions: Vec<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl Holiday {
+    /// The typed code of the holiday's country, without the subdivision of a
+    /// regional holiday, e.g. `US` for `US-CA`. `None` if the code is unknown.
+    pub fn country_code(&self) -> Option<CountryCode> {
+        let country = self.country.split('-').next().unwrap_or_default();
+        country.parse().ok()
+    }
+
+    /// Whether the holiday only applies to some subdivisions of its country.
+    pub fn is_regional(&self) -> bool {
+        !self.subdivisions.is_empty() || self.country.contains('-')
+    }
+
+    /// Whether the holiday is observed on another day than its `date`, e.g.
+    /// moved to the Friday when it falls on a Saturday.
+    pub fn is_shifted(&self) -> bool {
+        self.date != self.observed
+    }
+
+    /// Whether the holiday is observed from Monday to Friday, based on the
+    /// ISO number of its observed weekday.
+    pub fn is_observed_on_weekday(&self) -> bool {
+        self.weekday
+            .observed
+            .iso_weekday()
+            .is_some_and(|day| day <= 5)
+    }
+
+    /// Parses `(date, observed)`, i.e. the actual date of the holiday and the
+    /// day it is observed on.
+    #[cfg(feature = "chrono")]
+    pub fn dates(&self) -> Result<(NaiveDate, NaiveDate), chrono::ParseError> {
+        let parse = |date: &str| NaiveDate::parse_from_str(date, "%Y-%m-%d");
+        Ok((parse(&self.date)?, parse(&self.observed)?))
+    }
+
+    /// Day of the year of `date`, from 1 to 366, e.g. 60 for February 29th
+    /// and 61 for March 1st in a leap year, to put several years on a common
+    /// axis.
+    #[cfg(feature = "chrono")]
+    pub fn day_of_year(&self) -> Result<u32, chrono::ParseError> {
+        Ok(NaiveDate::parse_from_str(&self.date, "%Y-%m-%d")?.ordinal())
+    }
+}
+
+/// A minimal `{ title, date }` view of a `Holiday`, e.g. for a calendar
+/// frontend.
+///
+/// Collect a list with `holidays.into_iter().map(Event::from).collect()` or
+/// `Event::from_holidays`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Event {
+    /// Name of the holiday.
+    pub title: String,
+    /// Actual date of the holiday, formatted as `YYYY-MM-DD`, not the day it
+    /// is observed on.
+    pub date: String,
+}
+
+impl Event {
+    /// Converts every holiday of `holidays`, keeping their order.
+    pub fn from_holidays(holidays: impl IntoIterator<Item = Holiday>) -> Vec<Event> {
+        holidays.into_iter().map(Event::from).collect()
+    }
+}
+
+impl From<Holiday> for Event {
+    fn from(holiday: Holiday) -> Self {
+        Event {
+            title: holiday.name,
+            date: holiday.date,
+        }
+    }
+}
+
+impl From<&Holiday> for Event {
+    fn from(holiday: &Holiday) -> Self {
+        Event {
+            title: holiday.name.clone(),
+            date: holiday.date.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Weekday {
     pub date: Date,
     pub observed: Date,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+/// A day of the week.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Date {
+    /// Name of the day, localized according to the request's `language`
+    /// (e.g. `水曜日` instead of `Wednesday`). Do not parse it.
     pub name: String,
+    /// ISO 8601 day number, from `1` for Monday to `7` for Sunday, regardless
+    /// of the request's `language`.
     pub numeric: String,
 }
 
+impl Date {
+    /// Returns the ISO 8601 day number, from 1 for Monday to 7 for Sunday.
+    ///
+    /// Unlike `name`, this does not depend on the request's `language`.
+    pub fn iso_weekday(&self) -> Option<u8> {
+        self.numeric
+            .trim()
+            .parse()
+            .ok()
+            .filter(|day| (1..=7).contains(day))
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct WorkdayResponse {
     pub requests: APIRequests,
     pub status: u32,
@@ -85,7 +387,7 @@ pub struct WorkdayResponse {
 }
 
 #[derive(Debug, Deserialize, Clone)]
-
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct WorkdaysResponse {
     pub requests: APIRequests,
     pub status: u32,
@@ -95,16 +397,338 @@ pub struct WorkdaysResponse {
 }
 
 #[derive(Debug, Deserialize, Clone)]
-
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct LanguagesResponse {
     pub requests: APIRequests,
     pub status: u32,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub languages: Vec<Language>,
     pub error: Option<String>,
     pub warning: Option<String>,
 }
 #[derive(Debug, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Language {
     pub code: String,
     pub name: String,
 }
+
+impl ApiResponse for CountriesResponse {
+    const ENDPOINT: &'static str = "countries";
+    fn api_warning(&self) -> Option<ApiWarning> {
+        self.warning.as_deref().map(ApiWarning::parse)
+    }
+}
+
+impl ApiResponse for HolidaysResponse {
+    const ENDPOINT: &'static str = "holidays";
+    fn api_warning(&self) -> Option<ApiWarning> {
+        self.warning.as_deref().map(ApiWarning::parse)
+    }
+}
+
+impl ApiResponse for WorkdayResponse {
+    const ENDPOINT: &'static str = "workday";
+    fn api_warning(&self) -> Option<ApiWarning> {
+        self.warning.as_deref().map(ApiWarning::parse)
+    }
+}
+
+impl ApiResponse for WorkdaysResponse {
+    const ENDPOINT: &'static str = "workdays";
+    fn api_warning(&self) -> Option<ApiWarning> {
+        self.warning.as_deref().map(ApiWarning::parse)
+    }
+}
+
+impl ApiResponse for LanguagesResponse {
+    const ENDPOINT: &'static str = "languages";
+    fn api_warning(&self) -> Option<ApiWarning> {
+        self.warning.as_deref().map(ApiWarning::parse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static REQUESTS: &str =
+        r#""requests": {"used": 1, "available": 9999, "resets": "2020-01-01 00:00:00"}"#;
+
+    #[test]
+    fn test_empty_holidays() {
+        for holidays in [r#", "holidays": []"#, r#", "holidays": null"#, ""] {
+            let body = format!(r#"{{"status": 200, {}{}}}"#, REQUESTS, holidays);
+            let response: HolidaysResponse = serde_json::from_str(&body).unwrap();
+            assert!(response.holidays.is_empty(), "Should be empty for {}", body);
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_resets_at() {
+        let requests = |resets: &str| APIRequests {
+            available: 9999,
+            used: 1,
+            resets: resets.into(),
+        };
+        let expected = "2019-10-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(requests("2019-10-01 00:00:00").resets_at(), Ok(expected));
+        assert_eq!(
+            requests("2019-10-01T09:00:00+09:00").resets_at(),
+            Ok(expected)
+        );
+        assert!(requests("tomorrow").resets_at().is_err());
+    }
+
+    #[test]
+    fn test_event() {
+        let holidays: HolidaysResponse = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/fixtures/holidays.json"
+        )))
+        .unwrap();
+        let independence_day = Event::from(&holidays.holidays[1]);
+        assert_eq!(
+            serde_json::to_value(&independence_day).unwrap(),
+            serde_json::json!({ "title": "Independence Day", "date": "2020-07-04" })
+        );
+
+        let events = Event::from_holidays(holidays.holidays);
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[1], independence_day);
+    }
+
+    #[test]
+    fn test_subdivision_names() {
+        let subdivision: Subdivision = serde_json::from_str(
+            r#"{"code": "CA-QC", "name": "Qu\u00e9bec", "languages": ["fr"]}"#,
+        )
+        .unwrap();
+        assert_eq!(subdivision.name, "Québec");
+        let subdivision: Subdivision =
+            serde_json::from_str(r#"{"code": "JP-13", "name": "東京都", "languages": null}"#)
+                .unwrap();
+        assert_eq!(subdivision.name, "東京都");
+        assert!(subdivision.languages.is_empty());
+    }
+
+    #[test]
+    fn test_country_code() {
+        let holidays: HolidaysResponse = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/fixtures/holidays.json"
+        )))
+        .unwrap();
+        let mut holiday = holidays.holidays[0].clone();
+        assert_eq!(holiday.country_code().unwrap().as_str(), "US");
+        holiday.country = "US-CA".into();
+        assert_eq!(holiday.country_code().unwrap().as_str(), "US");
+        holiday.country = "XX".into();
+        assert_eq!(holiday.country_code(), None);
+        assert_eq!(holiday.country, "XX");
+    }
+
+    #[test]
+    fn test_pagination() {
+        let response = |pagination: &str| -> HolidaysResponse {
+            let body = format!(r#"{{"status": 200, {}{}}}"#, REQUESTS, pagination);
+            serde_json::from_str(&body).unwrap()
+        };
+        assert_eq!(response("").pagination, None);
+        assert_eq!(response(r#", "pagination": null"#).pagination, None);
+        assert_eq!(response(r#", "pagination": "2 of 3""#).pagination, None);
+        assert_eq!(
+            response(r#", "pagination": {"next_page": 2, "total": 3}"#).pagination,
+            Some(Pagination {
+                next_page: Some(2),
+                next_cursor: None
+            })
+        );
+    }
+
+    #[test]
+    fn test_flag() {
+        let countries: CountriesResponse = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/fixtures/countries.json"
+        )))
+        .unwrap();
+        let japan = countries.countries[0].clone();
+        assert_eq!(
+            japan.flag_url(),
+            Some("https://holidayapi.com/flags/jp.png")
+        );
+        assert_eq!(japan.flag_emoji().as_deref(), Some("🇯🇵"));
+
+        let emoji = Country {
+            flag: "🇯🇵".into(),
+            ..japan.clone()
+        };
+        assert_eq!(emoji.flag_url(), None);
+        assert_eq!(emoji.flag_emoji().as_deref(), Some("🇯🇵"));
+
+        let mut unknown = Country {
+            flag: "".into(),
+            ..japan
+        };
+        unknown.codes.alpha_2 = "J1".into();
+        assert_eq!(unknown.flag_url(), None);
+        assert_eq!(unknown.flag_emoji(), None);
+    }
+
+    #[test]
+    fn test_api_warning() {
+        let deprecation = |parameter: &str, message: &str| {
+            ApiWarning::Deprecation(Deprecation {
+                parameter: parameter.into(),
+                message: message.into(),
+            })
+        };
+        for (warning, parameter) in [
+            (
+                "The `country` parameter is deprecated, use `countries`.",
+                "country",
+            ),
+            ("Parameter 'year' has been deprecated.", "year"),
+            ("pretty parameter is deprecated", "pretty"),
+            ("\"upcoming\" was deprecated", "upcoming"),
+        ] {
+            assert_eq!(ApiWarning::parse(warning), deprecation(parameter, warning));
+        }
+        for other in [
+            "These results do not include state and province holidays.",
+            "This endpoint is deprecated.",
+            "Version 1 is deprecated, use version 2.",
+            "pretty is deprecated",
+        ] {
+            assert_eq!(ApiWarning::parse(other), ApiWarning::Other(other.into()));
+        }
+
+        let body = format!(
+            r#"{{"status": 200, {}, "warning": "The `day` parameter is deprecated."}}"#,
+            REQUESTS
+        );
+        let response: HolidaysResponse = serde_json::from_str(&body).unwrap();
+        assert!(matches!(
+            response.api_warning(),
+            Some(ApiWarning::Deprecation(Deprecation { parameter, .. })) if parameter == "day"
+        ));
+    }
+
+    #[test]
+    fn test_api_error() {
+        let error: ApiError = serde_json::from_str(
+            r#"{"status": 401, "error": "Invalid or expired key.", "requests": null}"#,
+        )
+        .unwrap();
+        assert_eq!(error.status, 401);
+        assert_eq!(error.error, "Invalid or expired key.");
+    }
+
+    #[test]
+    fn test_localized_weekday() {
+        let holiday: Holiday = serde_json::from_str(
+            r#"{
+                "name": "元日",
+                "date": "2020-01-01",
+                "observed": "2020-01-01",
+                "public": true,
+                "country": "JP",
+                "uuid": "c1e7d1a0-7c9b-4d6b-8f44-0e3bb3a1f9e2",
+                "weekday": {
+                    "date": { "name": "水曜日", "numeric": "3" },
+                    "observed": { "name": "水曜日", "numeric": "3" }
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(holiday.weekday.date.name, "水曜日");
+        assert_eq!(holiday.weekday.date.iso_weekday(), Some(3));
+        assert_eq!(holiday.weekday.observed.iso_weekday(), Some(3));
+        assert!(!holiday.is_shifted());
+
+        let shifted = Holiday {
+            observed: "2020-01-02".into(),
+            ..holiday
+        };
+        assert!(shifted.is_shifted());
+
+        #[cfg(feature = "chrono")]
+        {
+            let date = |day| NaiveDate::from_ymd_opt(2020, 1, day).unwrap();
+            assert_eq!(shifted.dates(), Ok((date(1), date(2))));
+            let malformed = Holiday {
+                observed: "2020-01-32".into(),
+                ..shifted.clone()
+            };
+            assert!(malformed.dates().is_err());
+
+            assert_eq!(shifted.day_of_year(), Ok(1));
+            for (date, day) in [
+                ("2020-02-29", 60),
+                ("2020-03-01", 61),
+                ("2021-03-01", 60),
+                ("2020-12-31", 366),
+                ("2021-12-31", 365),
+            ] {
+                let holiday = Holiday {
+                    date: date.into(),
+                    ..shifted.clone()
+                };
+                assert_eq!(holiday.day_of_year(), Ok(day), "{}", date);
+            }
+            let malformed = Holiday {
+                date: "2021-02-29".into(),
+                ..shifted.clone()
+            };
+            assert!(malformed.day_of_year().is_err());
+        }
+
+        let invalid = Date {
+            name: "".into(),
+            numeric: "8".into(),
+        };
+        assert_eq!(invalid.iso_weekday(), None);
+    }
+
+    #[test]
+    fn test_missing_vec_fields() {
+        let body = format!(
+            r#"{{
+                "status": 200,
+                {},
+                "countries": [{{
+                    "code": "AQ",
+                    "name": "Antarctica",
+                    "codes": {{"alpha-2": "AQ", "alpha-3": "ATA", "numeric": "010"}},
+                    "flag": "https://holidayapi.com/flags/aq.png",
+                    "languages": null
+                }}]
+            }}"#,
+            REQUESTS
+        );
+        let response: CountriesResponse = serde_json::from_str(&body).unwrap();
+        let country = &response.countries[0];
+        assert!(country.languages.is_empty());
+        assert!(country.subdivisions.is_empty());
+
+        let body = format!(
+            r#"{{"status": 200, {}, "countries": [{{
+                "code": "US",
+                "name": "United States",
+                "codes": {{"alpha-2": "US", "alpha-3": "USA", "numeric": "840"}},
+                "flag": "https://holidayapi.com/flags/us.png",
+                "languages": ["en"],
+                "subdivisions": [{{"code": "US-CA", "name": "California"}}]
+            }}]}}"#,
+            REQUESTS
+        );
+        let response: CountriesResponse = serde_json::from_str(&body).unwrap();
+        assert!(response.countries[0].subdivisions[0].languages.is_empty());
+
+        let body = format!(r#"{{"status": 200, {}}}"#, REQUESTS);
+        let response: LanguagesResponse = serde_json::from_str(&body).unwrap();
+        assert!(response.languages.is_empty());
+    }
+}
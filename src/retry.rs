@@ -0,0 +1,148 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use crate::HolidayAPIError;
+
+type Predicate = Arc<dyn Fn(&HolidayAPIError) -> bool + Send + Sync>;
+
+/// When and how often a failed request is sent again. See
+/// `HolidayAPIBuilder::retry`.
+///
+/// Waits `base_delay` before the first retry and doubles the delay for every
+/// following one, up to `max_delay`.
+///
+/// # Examples
+/// ```
+/// use holidayapi_rust::prelude::*;
+/// use std::time::Duration;
+///
+/// // Also retry timeouts and connection failures.
+/// let retry = RetryPolicy::new(3)
+///     .base_delay(Duration::from_millis(200))
+///     .should_retry(|err| {
+///         RetryPolicy::is_retryable(err)
+///             || matches!(err, HolidayAPIError::RequestError(e, _) if e.is_timeout() || e.is_connect())
+///     });
+/// let api = HolidayAPI::builder("00000000-0000-0000-0000-000000000000")
+///     .retry(retry)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    should_retry: Predicate,
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_retries", &self.max_retries)
+            .field("base_delay", &self.base_delay)
+            .field("max_delay", &self.max_delay)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RetryPolicy {
+    /// Retries a request up to `max_retries` times, on the errors accepted by
+    /// `RetryPolicy::is_retryable`, after 500ms, then 1s, 2s... up to 30s.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            should_retry: Arc::new(Self::is_retryable),
+        }
+    }
+
+    /// Delay before the first retry. Defaults to 500ms.
+    pub fn base_delay(&mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self.to_owned()
+    }
+
+    /// Longest delay between two attempts, which the doubling delay never
+    /// exceeds, to bound how long a request can take. Defaults to 30s.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// // Waits 1s, 2s, 4s, then 5s for every following retry.
+    /// let retry = RetryPolicy::new(8)
+    ///     .base_delay(Duration::from_secs(1))
+    ///     .max_delay(Duration::from_secs(5));
+    /// ```
+    pub fn max_delay(&mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self.to_owned()
+    }
+
+    /// Decides which errors are worth another attempt, instead of
+    /// `RetryPolicy::is_retryable`.
+    pub fn should_retry(
+        &mut self,
+        predicate: impl Fn(&HolidayAPIError) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.should_retry = Arc::new(predicate);
+        self.to_owned()
+    }
+
+    /// The default predicate: retries responses with a `429 Too Many
+    /// Requests` or `5xx` status, which are usually temporary. Rejected keys
+    /// and other client errors are not retried.
+    pub fn is_retryable(err: &HolidayAPIError) -> bool {
+        match err {
+            HolidayAPIError::RequestError(err, _) => err.status().is_some_and(|status| {
+                status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+            }),
+            HolidayAPIError::Shared(err) => Self::is_retryable(err),
+            _ => false,
+        }
+    }
+
+    /// Delay before retry number `retry`, counted from 0, or `None` when
+    /// `err` should not be retried.
+    pub(crate) fn delay(&self, retry: u32, err: &HolidayAPIError) -> Option<Duration> {
+        if retry >= self.max_retries || !(self.should_retry)(err) {
+            return None;
+        }
+        let delay = self.base_delay.saturating_mul(2u32.saturating_pow(retry));
+        Some(delay.min(self.max_delay))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay() {
+        let retry = RetryPolicy::new(3).base_delay(Duration::from_millis(100));
+        let err = HolidayAPIError::InvalidResponse("".into());
+        let always = retry.clone().should_retry(|_| true);
+
+        let delays: Vec<_> = (0..4).map(|retry| always.delay(retry, &err)).collect();
+        let ms = |ms| Some(Duration::from_millis(ms));
+        assert_eq!(delays, [ms(100), ms(200), ms(400), None]);
+        assert_eq!(retry.delay(0, &err), None);
+    }
+
+    #[test]
+    fn test_max_delay() {
+        let err = HolidayAPIError::InvalidResponse("".into());
+        let retry = RetryPolicy::new(u32::MAX).should_retry(|_| true);
+        assert_eq!(retry.delay(40, &err), Some(Duration::from_secs(30)));
+
+        let capped = retry.clone().max_delay(Duration::from_millis(1500));
+        let delays: Vec<_> = (0..4).map(|retry| capped.delay(retry, &err)).collect();
+        let ms = |ms| Some(Duration::from_millis(ms));
+        assert_eq!(delays, [ms(500), ms(1000), ms(1500), ms(1500)]);
+
+        let below_base = retry.clone().max_delay(Duration::from_millis(100));
+        assert_eq!(below_base.delay(0, &err), ms(100));
+    }
+}
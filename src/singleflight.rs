@@ -0,0 +1,129 @@
+use futures_util::future::{BoxFuture, FutureExt, Shared};
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use crate::HolidayAPIError;
+
+type Flight = Shared<BoxFuture<'static, Result<String, Arc<HolidayAPIError>>>>;
+
+/// Lets concurrent identical requests share a single call and its result.
+#[derive(Default)]
+pub(crate) struct Singleflight {
+    in_flight: Mutex<HashMap<String, Flight>>,
+}
+
+impl fmt::Debug for Singleflight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Singleflight")
+            .field("in_flight", &self.lock().len())
+            .finish()
+    }
+}
+
+impl Singleflight {
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Flight>> {
+        // The map is never left half updated, so a poisoned lock is still usable.
+        self.in_flight.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Runs `fetch`, unless a call with the same `key` is already in flight,
+    /// in which case its result is awaited instead.
+    pub(crate) async fn run<F>(&self, key: String, fetch: F) -> Result<String, HolidayAPIError>
+    where
+        F: Future<Output = Result<String, HolidayAPIError>> + Send + 'static,
+    {
+        let flight = self
+            .lock()
+            .entry(key.clone())
+            .or_insert_with(|| {
+                fetch
+                    .map(|result| result.map_err(Arc::new))
+                    .boxed()
+                    .shared()
+            })
+            .clone();
+        let result = flight.clone().await;
+
+        // The first caller to finish retires the flight, so that later calls
+        // go to the network again.
+        let mut in_flight = self.lock();
+        if in_flight
+            .get(&key)
+            .is_some_and(|current| current.ptr_eq(&flight))
+        {
+            in_flight.remove(&key);
+        }
+        drop(in_flight);
+        drop(flight);
+
+        result.map_err(|err| Arc::try_unwrap(err).unwrap_or_else(HolidayAPIError::Shared))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    fn fetch(
+        calls: &Arc<AtomicUsize>,
+        result: Result<String, HolidayAPIError>,
+    ) -> impl Future<Output = Result<String, HolidayAPIError>> + Send + 'static {
+        let calls = calls.clone();
+        async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            result
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_calls_are_shared() {
+        let singleflight = Singleflight::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let (a, b, c) = tokio::join!(
+            singleflight.run("holidays?year=2020".into(), fetch(&calls, Ok("a".into()))),
+            singleflight.run("holidays?year=2020".into(), fetch(&calls, Ok("b".into()))),
+            singleflight.run("holidays?year=2021".into(), fetch(&calls, Ok("c".into()))),
+        );
+        assert_eq!(
+            (a.unwrap(), b.unwrap(), c.unwrap()),
+            ("a".into(), "a".into(), "c".into())
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let retry = singleflight.run("holidays?year=2020".into(), fetch(&calls, Ok("d".into())));
+        assert_eq!(retry.await.unwrap(), "d");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert!(singleflight.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_shared() {
+        let singleflight = Singleflight::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let error = || Err(HolidayAPIError::InvalidOrExpiredKey("expired".into()));
+
+        let (a, b) = tokio::join!(
+            singleflight.run("countries".into(), fetch(&calls, error())),
+            singleflight.run("countries".into(), fetch(&calls, error())),
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        for result in [a, b] {
+            let err = result.unwrap_err();
+            assert_eq!(err.to_string(), "Invalid or expired key: expired");
+            assert!(matches!(
+                err,
+                HolidayAPIError::InvalidOrExpiredKey(_) | HolidayAPIError::Shared(_)
+            ));
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use futures_util::{stream, Stream};
+use reqwest::Response;
+use std::collections::VecDeque;
+
+use crate::{responses::Holiday, HolidayAPIError};
+
+/// Turns a `holidays` response into a stream of holidays, parsing each item of
+/// the `holidays` array as soon as its closing brace has been received.
+pub(crate) fn holidays(
+    response: Response,
+    max_bytes: Option<usize>,
+) -> impl Stream<Item = Result<Holiday, HolidayAPIError>> {
+    let scanner = Scanner {
+        max_bytes,
+        ..Scanner::default()
+    };
+    let state = (Some(response), scanner, VecDeque::new());
+
+    stream::unfold(
+        state,
+        |(mut response, mut scanner, mut pending)| async move {
+            loop {
+                if let Some(item) = pending.pop_front() {
+                    return Some((item, (response, scanner, pending)));
+                }
+                let body = response.as_mut()?;
+                match body.chunk().await {
+                    Ok(Some(chunk)) => {
+                        scanner.received += chunk.len();
+                        if let Some(max) = scanner.max_bytes.filter(|max| scanner.received > *max) {
+                            response = None;
+                            pending.push_back(Err(HolidayAPIError::ResponseTooLarge(max)));
+                        } else {
+                            pending.extend(scanner.feed(&chunk));
+                        }
+                    }
+                    Ok(None) => {
+                        response = None;
+                        if scanner.received == 0 {
+                            pending.push_back(Err(HolidayAPIError::EmptyResponse));
+                        } else if !scanner.is_done() {
+                            pending.push_back(Err(HolidayAPIError::InvalidResponse(
+                                "Response ended before the holidays array was complete".into(),
+                            )));
+                        }
+                    }
+                    Err(err) => {
+                        response = None;
+                        pending.push_back(Err(HolidayAPIError::RequestError(err, "".into())));
+                    }
+                }
+            }
+        },
+    )
+}
+
+#[derive(Debug, Default, PartialEq)]
+enum Phase {
+    /// Looking for the top level `holidays` key.
+    #[default]
+    Seeking,
+    /// Inside the `holidays` array.
+    Items,
+    /// The `holidays` array, or the response without one, has been closed.
+    Done,
+}
+
+/// Where the scanner is within a top level `"key": value` pair.
+#[derive(Debug, Default, PartialEq)]
+enum Pair {
+    #[default]
+    Key,
+    Colon {
+        holidays: bool,
+    },
+    Value {
+        holidays: bool,
+    },
+    Rest,
+}
+
+#[derive(Debug, Default)]
+struct Scanner {
+    max_bytes: Option<usize>,
+    received: usize,
+    buffer: Vec<u8>,
+    position: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    key_start: Option<usize>,
+    item_start: Option<usize>,
+    phase: Phase,
+    pair: Pair,
+}
+
+impl Scanner {
+    fn is_done(&self) -> bool {
+        self.phase == Phase::Done
+    }
+
+    /// Appends `chunk` and returns every holiday completed by it.
+    fn feed(&mut self, chunk: &[u8]) -> Vec<Result<Holiday, HolidayAPIError>> {
+        let mut items = Vec::new();
+        if self.is_done() {
+            return items;
+        }
+        self.buffer.extend_from_slice(chunk);
+
+        while self.position < self.buffer.len() && !self.is_done() {
+            let byte = self.buffer[self.position];
+            if self.in_string {
+                self.scan_string(byte);
+            } else if self.phase == Phase::Seeking {
+                self.scan_outer(byte);
+            } else if let Some(item) = self.scan_item(byte) {
+                items.push(item);
+            }
+            self.position += 1;
+        }
+
+        self.compact();
+        items
+    }
+
+    fn scan_string(&mut self, byte: u8) {
+        if self.escaped {
+            self.escaped = false;
+        } else if byte == b'\\' {
+            self.escaped = true;
+        } else if byte == b'"' {
+            self.in_string = false;
+            if let Some(start) = self.key_start.take() {
+                let holidays = &self.buffer[start..self.position] == b"holidays";
+                self.pair = Pair::Colon { holidays };
+            }
+        }
+    }
+
+    fn scan_outer(&mut self, byte: u8) {
+        if byte.is_ascii_whitespace() {
+            return;
+        }
+        let top_level = self.depth == 1;
+        match byte {
+            b'"' => {
+                self.in_string = true;
+                if top_level && self.pair == Pair::Key {
+                    self.key_start = Some(self.position + 1);
+                }
+            }
+            b':' if top_level => {
+                if let Pair::Colon { holidays } = self.pair {
+                    self.pair = Pair::Value { holidays };
+                }
+            }
+            b',' if top_level => self.pair = Pair::Key,
+            b'[' if top_level && self.pair == (Pair::Value { holidays: true }) => {
+                self.depth += 1;
+                self.phase = Phase::Items;
+            }
+            b'{' | b'[' => self.depth += 1,
+            b'}' | b']' => {
+                self.depth = self.depth.saturating_sub(1);
+                // A response without a `holidays` array, or with `null`, has
+                // no holidays to stream.
+                if self.depth == 0 {
+                    self.phase = Phase::Done;
+                }
+            }
+            _ => {}
+        }
+        if top_level && matches!(self.pair, Pair::Value { .. }) && byte != b':' {
+            self.pair = Pair::Rest;
+        }
+    }
+
+    fn scan_item(&mut self, byte: u8) -> Option<Result<Holiday, HolidayAPIError>> {
+        match byte {
+            b'"' => self.in_string = true,
+            b'{' | b'[' => {
+                if self.depth == 2 {
+                    self.item_start = Some(self.position);
+                }
+                self.depth += 1;
+            }
+            b'}' | b']' => {
+                self.depth -= 1;
+                if self.depth == 1 {
+                    self.phase = Phase::Done;
+                } else if self.depth == 2 {
+                    let start = self.item_start.take()?;
+                    let item = &self.buffer[start..=self.position];
+                    return Some(serde_json::from_slice(item).map_err(|err| {
+                        HolidayAPIError::parse(err, &String::from_utf8_lossy(item))
+                    }));
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Drops the bytes that are no longer needed to parse upcoming items.
+    fn compact(&mut self) {
+        let keep = self.item_start.or(self.key_start).unwrap_or(self.position);
+        self.buffer.drain(..keep);
+        self.position -= keep;
+        self.item_start = self.item_start.map(|start| start - keep);
+        self.key_start = self.key_start.map(|start| start - keep);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static BODY: &str = r#"{
+        "status": 200,
+        "warning": "holidays",
+        "requests": { "used": 1, "available": 9999, "resets": "2019-10-01 00:00:00" },
+        "nested": { "holidays": [{ "name": "decoy" }] },
+        "holidays": [
+            {
+                "name": "New Year's Day {\"quoted\"}",
+                "date": "2020-01-01",
+                "observed": "2020-01-01",
+                "public": true,
+                "country": "US",
+                "uuid": "82f78b8a-019e-479e-a19f-99040275f9bf",
+                "weekday": {
+                    "date": { "name": "Wednesday", "numeric": "3" },
+                    "observed": { "name": "Wednesday", "numeric": "3" }
+                }
+            },
+            {
+                "name": "Independence Day ]",
+                "date": "2020-07-04",
+                "observed": "2020-07-03",
+                "public": true,
+                "country": "US",
+                "uuid": "88268759-9b90-468c-804f-b729b8418e7c",
+                "weekday": {
+                    "date": { "name": "Saturday", "numeric": "6" },
+                    "observed": { "name": "Friday", "numeric": "5" }
+                }
+            }
+        ]
+    }"#;
+
+    fn scan_in_chunks(size: usize) -> (Vec<Holiday>, Scanner) {
+        let mut scanner = Scanner::default();
+        let holidays = BODY
+            .as_bytes()
+            .chunks(size)
+            .flat_map(|chunk| scanner.feed(chunk))
+            .map(|item| item.expect("Holiday is valid"))
+            .collect();
+        (holidays, scanner)
+    }
+
+    #[test]
+    fn test_scanner_finds_every_holiday() {
+        for size in [1, 3, 16, BODY.len()] {
+            let (holidays, scanner) = scan_in_chunks(size);
+            let names: Vec<_> = holidays.iter().map(|h| h.name.as_str()).collect();
+            assert_eq!(names, ["New Year's Day {\"quoted\"}", "Independence Day ]"]);
+            assert!(scanner.is_done());
+        }
+    }
+
+    #[test]
+    fn test_scanner_does_not_buffer_parsed_items() {
+        let (_, scanner) = scan_in_chunks(16);
+        assert!(scanner.buffer.len() < 16);
+    }
+
+    #[test]
+    fn test_scanner_reports_invalid_items() {
+        let mut scanner = Scanner::default();
+        let items = scanner.feed(br#"{"holidays": [{"name": 1}]}"#);
+        assert_eq!(items.len(), 1);
+        assert!(matches!(items[0], Err(HolidayAPIError::Parse { .. })));
+    }
+}
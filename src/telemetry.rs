@@ -0,0 +1,104 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        OnceLock,
+    },
+    time::SystemTime,
+};
+
+use tracing::{field, Span};
+
+use crate::{HolidayAPIError, Response, REDACTED};
+
+/// Span wrapping a single API request. The key is never recorded.
+pub(crate) fn request_span(
+    endpoint: &str,
+    parameters: &HashMap<String, String>,
+    key: &str,
+    correlation_id: Option<&str>,
+) -> Span {
+    let correlation_id = correlation_id.map_or_else(generate_correlation_id, str::to_owned);
+    let parameters: HashMap<&str, &str> = parameters
+        .iter()
+        .map(|(name, value)| match name.as_str() {
+            "key" => (name.as_str(), REDACTED),
+            _ => (name.as_str(), value.as_str()),
+        })
+        .collect();
+
+    tracing::info_span!(
+        "holidayapi.request",
+        endpoint = %endpoint,
+        correlation_id = %redact(&correlation_id, key),
+        parameters = %redact(&format!("{:?}", parameters), key),
+        status = field::Empty,
+    )
+}
+
+pub(crate) fn record_outcome(span: &Span, result: &Result<Response, HolidayAPIError>, key: &str) {
+    let _entered = span.enter();
+    match result {
+        Ok(response) => {
+            span.record("status", response.status().as_u16());
+            tracing::debug!("request succeeded");
+        }
+        Err(err) => {
+            if let HolidayAPIError::RequestError(err, _) = err {
+                if let Some(status) = err.status() {
+                    span.record("status", status.as_u16());
+                }
+            }
+            tracing::warn!(error = %redact(&err.to_string(), key), "request failed");
+        }
+    }
+}
+
+/// A process-wide sequence number, prefixed with a seed taken at the first
+/// call so that IDs from several processes are unlikely to collide, e.g.
+/// `5f3a9c1e-7`.
+fn generate_correlation_id() -> String {
+    static SEED: OnceLock<u32> = OnceLock::new();
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    let seed = SEED.get_or_init(|| {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .subsec_nanos()
+            ^ std::process::id()
+    });
+    format!("{:08x}-{}", seed, NEXT.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Error messages embed the request url, which carries the key. An empty key
+/// would match between every character, so the text is kept as is.
+fn redact(text: &str, key: &str) -> String {
+    if key.is_empty() {
+        return text.to_owned();
+    }
+    text.replace(key, REDACTED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_correlation_id() {
+        let first = generate_correlation_id();
+        let second = generate_correlation_id();
+        assert_ne!(first, second);
+        assert_eq!(first.split('-').next(), second.split('-').next());
+    }
+
+    #[test]
+    fn test_redact() {
+        let key = "00000000-0000-0000-0000-000000000000";
+        let url = format!("https://holidayapi.com/v1/holidays?key={}", key);
+        assert_eq!(
+            redact(&url, key),
+            format!("https://holidayapi.com/v1/holidays?key={}", REDACTED)
+        );
+        assert_eq!(redact("request failed", ""), "request failed");
+    }
+}
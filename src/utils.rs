@@ -0,0 +1,577 @@
+#[cfg(feature = "chrono")]
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+#[cfg(feature = "chrono")]
+use std::collections::HashSet;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    io::{self, Write},
+};
+
+use crate::responses::{Country, Holiday};
+
+/// Concatenates two lists of holidays, e.g. from a national and a
+/// subdivision query, dropping duplicate entries by `uuid`.
+///
+/// When both lists contain the same holiday, the subdivision entry (see
+/// `Holiday::is_regional`) is kept. If neither or both are subdivision
+/// entries, the first one wins. Holidays keep the position of their first
+/// occurrence.
+///
+/// # Examples
+/// ```
+/// use holidayapi_rust::prelude::*;
+///
+/// async fn us_and_california(api: HolidayAPI) -> Result<Vec<Holiday>, HolidayAPIError> {
+///     let national = api.holidays("US", 2020).get().await?;
+///     let california = api.holidays("US-CA", 2020).subdivisions().get().await?;
+///     Ok(merge_holidays(national, california))
+/// }
+/// ```
+pub fn merge_holidays(a: Vec<Holiday>, b: Vec<Holiday>) -> Vec<Holiday> {
+    let mut merged: Vec<Holiday> = Vec::with_capacity(a.len() + b.len());
+    let mut positions: HashMap<String, usize> = HashMap::new();
+
+    for holiday in a.into_iter().chain(b) {
+        match positions.get(&holiday.uuid) {
+            Some(&i) => {
+                if holiday.is_regional() && !merged[i].is_regional() {
+                    merged[i] = holiday;
+                }
+            }
+            None => {
+                positions.insert(holiday.uuid.clone(), merged.len());
+                merged.push(holiday);
+            }
+        }
+    }
+    merged
+}
+
+/// Filters on lists of holidays, e.g. as returned by
+/// `Request<HolidaysResponse>::get`.
+pub trait HolidaysExt {
+    /// Keeps only the holidays observed from Monday to Friday, see
+    /// `Holiday::is_observed_on_weekday`, e.g. to count the days off that
+    /// affect a business.
+    ///
+    /// # Examples
+    /// ```
+    /// use holidayapi_rust::prelude::*;
+    ///
+    /// async fn days_off(api: HolidayAPI) -> Result<usize, HolidayAPIError> {
+    ///     let holidays = api.holidays("US", 2020).public(true).get().await?;
+    ///     Ok(holidays.weekday_holidays().len())
+    /// }
+    /// ```
+    fn weekday_holidays(self) -> Vec<Holiday>;
+}
+
+impl HolidaysExt for Vec<Holiday> {
+    fn weekday_holidays(mut self) -> Vec<Holiday> {
+        self.retain(Holiday::is_observed_on_weekday);
+        self
+    }
+}
+
+/// Whether a holiday falls on the same day every year, see
+/// `classify_holidays`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HolidayKind {
+    /// On the same month and day every year, e.g. Christmas.
+    Fixed,
+    /// On another day in some years, e.g. Easter or Thanksgiving.
+    Moveable,
+}
+
+/// Classifies holidays as fixed or moveable by comparing the month and day of
+/// their `date` across the years they appear in, e.g. the holidays of a
+/// country for 2020 and 2021.
+///
+/// Holidays are matched by name, normalized to lowercase words without
+/// apostrophes so that `New Year's Day` and `New Years Day` match, and the
+/// map is keyed by that normalized name. Holidays found in a single year
+/// cannot be compared and are left out.
+///
+/// # Examples
+/// ```
+/// use holidayapi_rust::prelude::*;
+///
+/// async fn moveable(api: HolidayAPI) -> Result<Vec<String>, HolidayAPIError> {
+///     let mut holidays = api.holidays("US", 2020).get().await?;
+///     holidays.extend(api.holidays("US", 2021).get().await?);
+///     Ok(classify_holidays(&holidays)
+///         .into_iter()
+///         .filter(|(_, kind)| *kind == HolidayKind::Moveable)
+///         .map(|(name, _)| name)
+///         .collect())
+/// }
+/// ```
+pub fn classify_holidays<'a>(
+    holidays: impl IntoIterator<Item = &'a Holiday>,
+) -> BTreeMap<String, HolidayKind> {
+    let mut dates: BTreeMap<String, (BTreeSet<&str>, BTreeSet<&str>)> = BTreeMap::new();
+    for holiday in holidays {
+        let (Some(year), Some(month_day)) = (holiday.date.get(..4), holiday.date.get(5..)) else {
+            continue;
+        };
+        let (years, month_days) = dates.entry(normalize_name(&holiday.name)).or_default();
+        years.insert(year);
+        month_days.insert(month_day);
+    }
+
+    dates
+        .into_iter()
+        .filter(|(_, (years, _))| years.len() > 1)
+        .map(|(name, (_, month_days))| {
+            let kind = match month_days.len() {
+                1 => HolidayKind::Fixed,
+                _ => HolidayKind::Moveable,
+            };
+            (name, kind)
+        })
+        .collect()
+}
+
+/// Lowercases `name` and keeps its words only, e.g. `new years day` for
+/// `New Year's Day`.
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '\'' | '\u{2019}'))
+        .collect::<String>()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Flattens the subdivisions of `countries` into
+/// `(country_code, subdivision_code, subdivision_name)` tuples.
+pub fn flatten_subdivisions(countries: &[Country]) -> Vec<(String, String, String)> {
+    countries
+        .iter()
+        .flat_map(|country| {
+            country.subdivisions.iter().map(|subdivision| {
+                (
+                    country.code.clone(),
+                    subdivision.code.clone(),
+                    subdivision.name.clone(),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Writes `holidays` to `writer` as newline-delimited JSON, one holiday per
+/// line, in the same shape as the API returns them.
+///
+/// Holidays are written one by one, so they can come from several requests
+/// without being collected first. `writer` is not buffered, wrap files in a
+/// `BufWriter`.
+///
+/// # Examples
+/// ```no_run
+/// use holidayapi_rust::prelude::*;
+/// use std::{fs::File, io::BufWriter};
+///
+/// async fn export(api: HolidayAPI) -> std::io::Result<()> {
+///     let mut holidays = Vec::new();
+///     for country in ["US", "JP"] {
+///         holidays.extend(api.holidays(country, 2020).get().await?);
+///     }
+///     let file = File::create("holidays.ndjson")?;
+///     export_ndjson(&holidays, BufWriter::new(file))
+/// }
+/// ```
+pub fn export_ndjson<'a, W: Write>(
+    holidays: impl IntoIterator<Item = &'a Holiday>,
+    mut writer: W,
+) -> io::Result<()> {
+    for holiday in holidays {
+        serde_json::to_writer(&mut writer, holiday)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()
+}
+
+/// Writes `holidays` to `writer` as CSV, with a header row, e.g. for a
+/// reporting pipeline. Names with commas, quotes or line breaks are quoted.
+///
+/// The columns are `name`, `date`, `observed`, `public`, `country`, `uuid`,
+/// `weekday` and `observed_weekday`, the names of the weekdays in the
+/// `language` of the response, and `subdivisions`, joined with `;`.
+/// Holidays are written one by one, as with `export_ndjson`, and `writer`
+/// is buffered.
+///
+/// # Examples
+/// ```no_run
+/// use holidayapi_rust::prelude::*;
+/// use std::fs::File;
+///
+/// async fn report(api: HolidayAPI) -> std::io::Result<()> {
+///     let holidays = api.holidays("US", 2020).get().await?;
+///     write_csv(&holidays, File::create("holidays.csv")?)
+/// }
+/// ```
+#[cfg(feature = "csv")]
+pub fn write_csv<'a, W: Write>(
+    holidays: impl IntoIterator<Item = &'a Holiday>,
+    writer: W,
+) -> io::Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record([
+        "name",
+        "date",
+        "observed",
+        "public",
+        "country",
+        "uuid",
+        "weekday",
+        "observed_weekday",
+        "subdivisions",
+    ])?;
+    for holiday in holidays {
+        writer.write_record([
+            holiday.name.as_str(),
+            &holiday.date,
+            &holiday.observed,
+            if holiday.public { "true" } else { "false" },
+            &holiday.country,
+            &holiday.uuid,
+            &holiday.weekday.date.name,
+            &holiday.weekday.observed.name,
+            &holiday.subdivisions.join(";"),
+        ])?;
+    }
+    writer.flush()
+}
+
+/// Groups the holidays of ISO week-year `year` by ISO 8601 week number,
+/// from 1 to 52 or 53, based on their `date`.
+///
+/// ISO weeks start on Monday and week 1 is the one containing the first
+/// Thursday of the year, so the first and last few days of a calendar year
+/// may belong to a neighbouring ISO year: 2021-01-01 is in week 53 of 2020
+/// and 2024-12-30 in week 1 of 2025. Such holidays, as well as those with an
+/// unparsable date, are left out rather than filed under the wrong week. To
+/// get every holiday of an ISO year, include the neighbouring calendar years.
+///
+/// # Examples
+/// ```
+/// use holidayapi_rust::prelude::*;
+///
+/// async fn planner(api: HolidayAPI) -> Result<(), HolidayAPIError> {
+///     let holidays = api.holidays("US", 2020).get().await?;
+///     for (week, holidays) in group_by_iso_week(holidays, 2020) {
+///         println!("week {week}: {} holidays", holidays.len());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "chrono")]
+pub fn group_by_iso_week(holidays: Vec<Holiday>, year: i32) -> BTreeMap<u32, Vec<Holiday>> {
+    let mut weeks: BTreeMap<u32, Vec<Holiday>> = BTreeMap::new();
+    for holiday in holidays {
+        let Ok(date) = NaiveDate::parse_from_str(&holiday.date, "%Y-%m-%d") else {
+            continue;
+        };
+        let week = date.iso_week();
+        if week.year() == year {
+            weeks.entry(week.week()).or_default().push(holiday);
+        }
+    }
+    weeks
+}
+
+/// Counts the workdays from `start` to `end`, both included, without a
+/// request, e.g. with holidays fetched already: every day that is neither in
+/// `weekend` nor the observed date of one of `holidays` is a workday.
+///
+/// Every holiday in `holidays` is a day off, whether public or not, so pass
+/// the result of a request with `public(true)` to match the `workdays`
+/// endpoint, which also treats Saturday and Sunday as the weekend. Holidays
+/// with a malformed `observed` date are ignored, as are holidays outside the
+/// range: the holidays of every year in the range are needed.
+///
+/// # Examples
+/// ```
+/// use chrono::{NaiveDate, Weekday};
+/// use holidayapi_rust::prelude::*;
+///
+/// async fn july(api: HolidayAPI) -> Result<u32, HolidayAPIError> {
+///     let holidays = api.holidays("US", 2020).public(true).get().await?;
+///     let start = NaiveDate::from_ymd_opt(2020, 7, 1).unwrap();
+///     let end = NaiveDate::from_ymd_opt(2020, 7, 31).unwrap();
+///     Ok(workdays_local(start, end, &holidays, &[Weekday::Sat, Weekday::Sun]))
+/// }
+/// ```
+#[cfg(feature = "chrono")]
+pub fn workdays_local(
+    start: NaiveDate,
+    end: NaiveDate,
+    holidays: &[Holiday],
+    weekend: &[Weekday],
+) -> u32 {
+    let (start, end) = (start.min(end), start.max(end));
+    let days_off: HashSet<NaiveDate> = holidays
+        .iter()
+        .filter_map(|holiday| NaiveDate::parse_from_str(&holiday.observed, "%Y-%m-%d").ok())
+        .collect();
+    let workdays = start
+        .iter_days()
+        .take_while(|date| *date <= end)
+        .filter(|date| !weekend.contains(&date.weekday()) && !days_off.contains(date))
+        .count();
+    workdays as u32
+}
+
+/// How a holiday falling on a weekend is moved to a weekday, see
+/// `compute_observed`.
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ShiftRule {
+    /// Observed on its date, whatever the day.
+    #[default]
+    None,
+    /// Saturday to the Friday before, Sunday to the Monday after, e.g. US
+    /// federal holidays.
+    NearestWeekday,
+    /// Saturday and Sunday to the Monday after, e.g. UK bank holidays.
+    NextMonday,
+    /// Sunday to the Monday after, Saturday unchanged, e.g. Japanese
+    /// substitute holidays.
+    SundayToMonday,
+}
+
+/// Returns the day a holiday on `date` is observed on under `rule`, e.g. to
+/// check the `observed` date of the API or fill it in for local holidays.
+///
+/// Only the weekend shift is applied: a holiday moved onto another one, like
+/// Boxing Day after a Christmas on Saturday, is not moved again, and neither
+/// are shifts decided case by case.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use holidayapi_rust::prelude::*;
+///
+/// let independence_day = NaiveDate::from_ymd_opt(2020, 7, 4).unwrap();
+/// assert_eq!(
+///     compute_observed(independence_day, ShiftRule::NearestWeekday),
+///     NaiveDate::from_ymd_opt(2020, 7, 3).unwrap()
+/// );
+/// ```
+#[cfg(feature = "chrono")]
+pub fn compute_observed(date: NaiveDate, rule: ShiftRule) -> NaiveDate {
+    let shift = match (rule, date.weekday()) {
+        (ShiftRule::NearestWeekday, Weekday::Sat) => return date - Days::new(1),
+        (ShiftRule::NearestWeekday | ShiftRule::SundayToMonday, Weekday::Sun) => 1,
+        (ShiftRule::NextMonday, Weekday::Sat) => 2,
+        (ShiftRule::NextMonday, Weekday::Sun) => 1,
+        _ => 0,
+    };
+    date + Days::new(shift)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holiday(uuid: &str, name: &str, subdivisions: &[&str]) -> Holiday {
+        dated(uuid, name, "2020-03-31", subdivisions)
+    }
+
+    fn dated(uuid: &str, name: &str, date: &str, subdivisions: &[&str]) -> Holiday {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "date": date,
+            "observed": date,
+            "public": false,
+            "country": "US",
+            "uuid": uuid,
+            "subdivisions": subdivisions,
+            "weekday": {
+                "date": { "name": "Tuesday", "numeric": "2" },
+                "observed": { "name": "Tuesday", "numeric": "2" }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_workdays_local() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2020, month, day).unwrap();
+        let holidays = [
+            dated("1", "Independence Day", "2020-07-03", &[]),
+            dated("2", "Malformed", "2020-07-32", &[]),
+        ];
+        let weekend = [Weekday::Sat, Weekday::Sun];
+        assert_eq!(
+            workdays_local(date(7, 1), date(7, 31), &holidays, &weekend),
+            22
+        );
+        assert_eq!(
+            workdays_local(date(7, 31), date(7, 1), &holidays, &weekend),
+            22
+        );
+        assert_eq!(workdays_local(date(7, 1), date(7, 31), &[], &weekend), 23);
+        assert_eq!(workdays_local(date(7, 3), date(7, 3), &holidays, &[]), 0);
+        let friday_saturday = [Weekday::Fri, Weekday::Sat];
+        assert_eq!(
+            workdays_local(date(7, 1), date(7, 31), &holidays, &friday_saturday),
+            22
+        );
+    }
+
+    #[test]
+    fn test_classify_holidays() {
+        let holidays = [
+            dated("1", "New Year's Day", "2020-01-01", &[]),
+            dated("2", "New Years  Day", "2021-01-01", &[]),
+            dated("3", "Easter Sunday", "2020-04-12", &[]),
+            dated("4", "Easter Sunday", "2021-04-04", &[]),
+            dated("5", "Election Day", "2020-11-03", &[]),
+        ];
+        assert_eq!(
+            classify_holidays(&holidays),
+            BTreeMap::from([
+                ("easter sunday".to_owned(), HolidayKind::Moveable),
+                ("new years day".to_owned(), HolidayKind::Fixed),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_export_ndjson() {
+        let holidays = [
+            holiday("1", "New Year's Day", &[]),
+            holiday("2", "César Chávez Day", &["US-CA"]),
+        ];
+        let mut out = Vec::new();
+        export_ndjson(&holidays, &mut out).unwrap();
+
+        let lines: Vec<_> = std::str::from_utf8(&out).unwrap().lines().collect();
+        assert_eq!(lines.len(), 2);
+        let parsed: Holiday = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(parsed.name, "César Chávez Day");
+        assert_eq!(parsed.subdivisions, ["US-CA"]);
+        assert_eq!(parsed.weekday.date.iso_weekday(), Some(2));
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn test_write_csv() {
+        let holidays = [
+            holiday("1", "New Year's Day", &[]),
+            holiday("2", "Day of \"Unity\", Peace", &["US-CA", "US-NV"]),
+        ];
+        let mut out = Vec::new();
+        write_csv(&holidays, &mut out).unwrap();
+
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "name,date,observed,public,country,uuid,weekday,observed_weekday,subdivisions",
+                "New Year's Day,2020-03-31,2020-03-31,false,US,1,Tuesday,Tuesday,",
+                r#""Day of ""Unity"", Peace",2020-03-31,2020-03-31,false,US,2,Tuesday,Tuesday,US-CA;US-NV"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_weekday_holidays() {
+        let on = |name: &str, numeric: &str| {
+            let mut holiday = holiday(name, name, &[]);
+            holiday.weekday.observed.numeric = numeric.into();
+            holiday
+        };
+        let holidays = vec![
+            on("monday", "1"),
+            on("saturday", "6"),
+            on("friday", "5"),
+            on("unknown", ""),
+        ];
+        let names: Vec<_> = holidays
+            .weekday_holidays()
+            .into_iter()
+            .map(|holiday| holiday.name)
+            .collect();
+        assert_eq!(names, ["monday", "friday"]);
+    }
+
+    #[test]
+    fn test_merge_holidays() {
+        let national = vec![
+            holiday("1", "New Year's Day", &[]),
+            holiday("2", "César Chávez Day", &[]),
+        ];
+        let regional = vec![
+            holiday("2", "César Chávez Day", &["US-CA"]),
+            holiday("3", "Admission Day", &["US-CA"]),
+            holiday("3", "Admission Day (duplicate)", &["US-CA"]),
+        ];
+
+        let merged = merge_holidays(national, regional);
+        let summary: Vec<_> = merged
+            .iter()
+            .map(|h| (h.uuid.as_str(), h.name.as_str(), h.is_regional()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("1", "New Year's Day", false),
+                ("2", "César Chávez Day", true),
+                ("3", "Admission Day", true),
+            ]
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_compute_observed() {
+        let day = |day| NaiveDate::from_ymd_opt(2020, 7, day).unwrap();
+        let (friday, saturday, sunday, monday) = (day(3), day(4), day(5), day(6));
+        let cases = [
+            (ShiftRule::None, [friday, saturday, sunday]),
+            (ShiftRule::NearestWeekday, [friday, friday, monday]),
+            (ShiftRule::NextMonday, [friday, monday, monday]),
+            (ShiftRule::SundayToMonday, [friday, saturday, monday]),
+        ];
+        for (rule, observed) in cases {
+            let computed = [friday, saturday, sunday].map(|date| compute_observed(date, rule));
+            assert_eq!(computed, observed, "{:?}", rule);
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_group_by_iso_week() {
+        let holidays = vec![
+            dated("1", "New Year's Day 2021", "2021-01-01", &[]),
+            dated("2", "Monday", "2021-01-04", &[]),
+            dated("3", "Tuesday", "2021-01-05", &[]),
+            dated("4", "Christmas Day", "2021-12-25", &[]),
+            dated("5", "New Year's Eve", "2021-12-31", &[]),
+            dated("6", "Invalid", "2021-13-01", &[]),
+            dated("7", "Next year's week 1", "2024-12-30", &[]),
+        ];
+
+        let weeks = group_by_iso_week(holidays.clone(), 2021);
+        let summary: Vec<_> = weeks
+            .iter()
+            .map(|(week, holidays)| (*week, holidays.iter().map(|h| h.uuid.as_str()).collect()))
+            .collect::<Vec<(u32, Vec<&str>)>>();
+        assert_eq!(
+            summary,
+            [(1, vec!["2", "3"]), (51, vec!["4"]), (52, vec!["5"])]
+        );
+
+        let weeks = group_by_iso_week(holidays.clone(), 2020);
+        assert_eq!(weeks.keys().collect::<Vec<_>>(), [&53]);
+        let weeks = group_by_iso_week(holidays, 2025);
+        assert_eq!(weeks[&1][0].uuid, "7");
+    }
+}
@@ -0,0 +1,265 @@
+use std::{error::Error, fmt};
+
+use reqwest::header::{HeaderName, HeaderValue};
+
+use crate::requests::Parameters;
+
+/// A request whose parameters cannot make a valid call, see
+/// `Request::validate`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// `previous` and `upcoming` cannot be combined.
+    PreviousAndUpcoming,
+    /// The parameter, `day`, `previous` or `upcoming`, requires a `month`.
+    MissingMonth(&'static str),
+    /// The parameter, `previous` or `upcoming`, requires a `day`.
+    MissingDay(&'static str),
+    /// The parameter is not a number within its range, e.g. a `month` of 13.
+    OutOfRange {
+        parameter: &'static str,
+        value: String,
+    },
+    /// The parameter is not a `YYYY-MM-DD` date, or not an existing one.
+    InvalidDate {
+        parameter: &'static str,
+        value: String,
+    },
+    /// A header set with `Request::header` has an invalid name or value.
+    InvalidHeader(String),
+    /// The parameter has fewer characters than the endpoint accepts, e.g. a
+    /// holidays `search` shorter than 5 characters.
+    TooShort {
+        parameter: &'static str,
+        minimum: usize,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::PreviousAndUpcoming => {
+                write!(f, "previous and upcoming cannot be used together")
+            }
+            ValidationError::MissingMonth(parameter) => write!(f, "{} requires a month", parameter),
+            ValidationError::MissingDay(parameter) => write!(f, "{} requires a day", parameter),
+            ValidationError::OutOfRange { parameter, value } => {
+                write!(f, "{} is out of range: {}", parameter, value)
+            }
+            ValidationError::InvalidDate { parameter, value } => {
+                write!(f, "{} is not a YYYY-MM-DD date: {}", parameter, value)
+            }
+            ValidationError::InvalidHeader(name) => write!(f, "invalid header: {}", name),
+            ValidationError::TooShort { parameter, minimum } => {
+                write!(f, "{} must have at least {} characters", parameter, minimum)
+            }
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+/// Checks every rule that applies to `parameters`. Parameters only exist on
+/// their own endpoint, so the rules do not depend on it.
+pub(crate) fn validate(parameters: &Parameters) -> Result<(), ValidationError> {
+    let get = |name: &str| parameters.get(name).map(|value| value.as_ref());
+
+    let previous = get("previous").is_some();
+    let upcoming = get("upcoming").is_some();
+    if previous && upcoming {
+        return Err(ValidationError::PreviousAndUpcoming);
+    }
+    let relative = match (previous, upcoming) {
+        (true, _) => Some("previous"),
+        (_, true) => Some("upcoming"),
+        _ => None,
+    };
+    if get("month").is_none() {
+        if let Some(parameter) = relative.or(get("day").map(|_| "day")) {
+            return Err(ValidationError::MissingMonth(parameter));
+        }
+    }
+    if let (Some(parameter), None) = (relative, get("day")) {
+        return Err(ValidationError::MissingDay(parameter));
+    }
+
+    let month = match get("month") {
+        Some(month) => Some(in_range("month", month, 1, 12)?),
+        None => None,
+    };
+    if let Some(day) = get("day") {
+        let year = get("year").and_then(|year| year.parse().ok());
+        let max = match (year, month) {
+            (Some(year), Some(month)) => days_in_month(year, month),
+            _ => 31,
+        };
+        in_range("day", day, 1, max)?;
+    }
+    for parameter in ["start", "end"] {
+        if let Some(date) = get(parameter) {
+            check_date(parameter, date)?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks that `name: value` can be sent as a header.
+pub(crate) fn validate_header(name: &str, value: &str) -> Result<(), ValidationError> {
+    match (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+        (Ok(_), Ok(_)) => Ok(()),
+        _ => Err(ValidationError::InvalidHeader(name.to_owned())),
+    }
+}
+
+fn in_range(
+    parameter: &'static str,
+    value: &str,
+    min: u32,
+    max: u32,
+) -> Result<u32, ValidationError> {
+    value
+        .parse()
+        .ok()
+        .filter(|number| (min..=max).contains(number))
+        .ok_or_else(|| ValidationError::OutOfRange {
+            parameter,
+            value: value.to_owned(),
+        })
+}
+
+fn check_date(parameter: &'static str, date: &str) -> Result<(), ValidationError> {
+    let invalid = || ValidationError::InvalidDate {
+        parameter,
+        value: date.to_owned(),
+    };
+    let parts: Vec<_> = date.split('-').collect();
+    let [year, month, day] = parts[..] else {
+        return Err(invalid());
+    };
+    let digits =
+        |part: &str, len: usize| part.len() == len && part.bytes().all(|b| b.is_ascii_digit());
+    if !(digits(year, 4) && digits(month, 2) && digits(day, 2)) {
+        return Err(invalid());
+    }
+    let (year, month, day): (i32, u32, u32) = (
+        year.parse().map_err(|_| invalid())?,
+        month.parse().map_err(|_| invalid())?,
+        day.parse().map_err(|_| invalid())?,
+    );
+    if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(parameters: &[(&'static str, &'static str)]) -> Result<(), ValidationError> {
+        validate(
+            &parameters
+                .iter()
+                .map(|&(name, value)| (name.into(), value.into()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_previous_and_upcoming() {
+        let both = [
+            ("month", "7"),
+            ("day", "4"),
+            ("previous", "true"),
+            ("upcoming", "true"),
+        ];
+        assert_eq!(check(&both), Err(ValidationError::PreviousAndUpcoming));
+        assert_eq!(check(&both[..3]), Ok(()));
+    }
+
+    #[test]
+    fn test_missing_month_and_day() {
+        assert_eq!(
+            check(&[("day", "4")]),
+            Err(ValidationError::MissingMonth("day"))
+        );
+        assert_eq!(
+            check(&[("upcoming", "true")]),
+            Err(ValidationError::MissingMonth("upcoming"))
+        );
+        assert_eq!(
+            check(&[("month", "7"), ("previous", "true")]),
+            Err(ValidationError::MissingDay("previous"))
+        );
+        assert_eq!(check(&[("month", "7")]), Ok(()));
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let out_of_range = |parameter, value: &str| {
+            Err(ValidationError::OutOfRange {
+                parameter,
+                value: value.into(),
+            })
+        };
+        assert_eq!(check(&[("month", "13")]), out_of_range("month", "13"));
+        assert_eq!(check(&[("month", "0")]), out_of_range("month", "0"));
+        assert_eq!(
+            check(&[("month", "7"), ("day", "32")]),
+            out_of_range("day", "32")
+        );
+        let february = [("year", "2021"), ("month", "2"), ("day", "29")];
+        assert_eq!(check(&february), out_of_range("day", "29"));
+        assert_eq!(
+            check(&[("year", "2020"), ("month", "2"), ("day", "29")]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        assert_eq!(validate_header("Idempotency-Key", "1234"), Ok(()));
+        for (name, value) in [
+            ("Idempotency Key", "1234"),
+            ("", "1234"),
+            ("X-Id", "12\n34"),
+        ] {
+            assert_eq!(
+                validate_header(name, value),
+                Err(ValidationError::InvalidHeader(name.into()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_date() {
+        for date in [
+            "2020-7-01",
+            "2020/07/01",
+            "2020-02-30",
+            "2020-13-01",
+            "today",
+            "+202-07-01",
+        ] {
+            assert_eq!(
+                check(&[("start", "2020-07-01"), ("end", date)]),
+                Err(ValidationError::InvalidDate {
+                    parameter: "end",
+                    value: date.into()
+                })
+            );
+        }
+        assert_eq!(
+            check(&[("start", "2020-02-29"), ("end", "2020-12-31")]),
+            Ok(())
+        );
+    }
+}
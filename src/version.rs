@@ -0,0 +1,105 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+use strum_macros::EnumIter;
+
+use crate::HolidayAPIError;
+
+/// A version of the API.
+///
+/// Accepted wherever a version is expected, as are its number (`1`) and its
+/// name (`"v1"`, `"V1"` or `"1"`). Adding a version takes a variant, an
+/// entry in `ALL` and its path segment in `as_str`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, EnumIter)]
+pub enum ApiVersion {
+    #[default]
+    V1,
+}
+
+impl ApiVersion {
+    /// Every supported version, oldest first.
+    pub const ALL: &'static [ApiVersion] = &[ApiVersion::V1];
+
+    /// Path segment of the version in the API's urls, e.g. `v1`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "v1",
+        }
+    }
+
+    fn invalid(version: impl fmt::Display) -> HolidayAPIError {
+        let names: Vec<_> = Self::ALL.iter().map(ApiVersion::as_str).collect();
+        HolidayAPIError::InvalidVersion(format!(
+            "Invalid version: {}, please choose: {:?}",
+            version, names
+        ))
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ApiVersion {
+    type Err = HolidayAPIError;
+
+    /// Parses `v1`, `V1` or `1`.
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        let number = version.strip_prefix(['v', 'V']).unwrap_or(version);
+        Self::ALL
+            .iter()
+            .find(|known| known.as_str()[1..] == *number)
+            .copied()
+            .ok_or_else(|| Self::invalid(version))
+    }
+}
+
+impl TryFrom<i32> for ApiVersion {
+    type Error = HolidayAPIError;
+
+    fn try_from(version: i32) -> Result<Self, Self::Error> {
+        version.to_string().parse()
+    }
+}
+
+impl TryFrom<&str> for ApiVersion {
+    type Error = HolidayAPIError;
+
+    fn try_from(version: &str) -> Result<Self, Self::Error> {
+        version.parse()
+    }
+}
+
+impl From<Infallible> for HolidayAPIError {
+    fn from(never: Infallible) -> Self {
+        match never {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_all_versions() {
+        assert_eq!(ApiVersion::ALL, ApiVersion::iter().collect::<Vec<_>>());
+        for version in ApiVersion::ALL {
+            assert_eq!(version.as_str(), version.to_string());
+            assert_eq!(ApiVersion::try_from(version.as_str()).ok(), Some(*version));
+        }
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(ApiVersion::try_from(1).ok(), Some(ApiVersion::V1));
+        assert_eq!(ApiVersion::try_from("V1").ok(), Some(ApiVersion::V1));
+        assert_eq!(ApiVersion::try_from("1").ok(), Some(ApiVersion::V1));
+        assert!(matches!(
+            ApiVersion::try_from(2),
+            Err(HolidayAPIError::InvalidVersion(_))
+        ));
+        assert!(ApiVersion::try_from("v1.1").is_err());
+    }
+}
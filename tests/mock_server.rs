@@ -0,0 +1,893 @@
+use futures_util::StreamExt;
+use holidayapi_rust::prelude::*;
+use reqwest::Url;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+use wiremock::{
+    matchers::{header, header_exists, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+static KEY: &str = "00000000-0000-0000-0000-000000000000";
+
+fn fixture(endpoint: &str) -> String {
+    let path = format!("{}/fixtures/{}.json", env!("CARGO_MANIFEST_DIR"), endpoint);
+    std::fs::read_to_string(path).unwrap()
+}
+
+fn json(body: String) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_raw(body, "application/json")
+}
+
+fn api(server: &MockServer, prefix: &str) -> HolidayAPI {
+    let base_url = Url::parse(&format!("{}{}", server.uri(), prefix)).unwrap();
+    HolidayAPI::with_base_url(KEY, base_url).unwrap()
+}
+
+#[tokio::test]
+async fn test_holidays() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/holidays"))
+        .and(query_param("key", KEY))
+        .and(query_param("country", "US"))
+        .and(query_param("year", "2020"))
+        .and(query_param("public", "true"))
+        .and(query_param("format", "json"))
+        .respond_with(json(fixture("holidays")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let holidays = api(&server, "/v1/")
+        .holidays("US", 2020)
+        .public(true)
+        .get()
+        .await
+        .unwrap();
+    let names: Vec<_> = holidays.iter().map(|h| h.name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "New Year's Day",
+            "Independence Day",
+            "Halloween",
+            "Christmas Day"
+        ]
+    );
+    assert!(holidays[1].is_shifted());
+}
+
+#[tokio::test]
+async fn test_preset_format() {
+    let server = MockServer::start().await;
+    Mock::given(path("/v1/languages"))
+        .and(query_param("format", "JSON"))
+        .respond_with(json(fixture("languages")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let languages = api(&server, "/v1/").languages().format("JSON").get().await;
+    assert_eq!(languages.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_raw_format() {
+    let server = MockServer::start().await;
+    Mock::given(path("/v1/workdays"))
+        .and(query_param("format", "json"))
+        .respond_with(json(fixture("workdays")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(path("/v1/workdays"))
+        .and(query_param("format", "csv"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("workdays\n21\n"))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let api = api(&server, "/v1/");
+    let mut request = api.workdays("US", "2020-07-01", "2020-07-31");
+    assert!(request.clone().get_raw().await.unwrap().starts_with('{'));
+    let csv = request.format(Format::Csv);
+    assert_eq!(csv.clone().get_raw().await.unwrap(), "workdays\n21\n");
+    assert_eq!(csv.get_bytes().await.unwrap(), b"workdays\n21\n");
+}
+
+#[tokio::test]
+async fn test_every_endpoint_path() {
+    let server = MockServer::start().await;
+    for endpoint in ["countries", "holidays", "languages", "workday", "workdays"] {
+        Mock::given(path(format!("/holidayapi/v1/{}", endpoint)))
+            .respond_with(json(fixture(endpoint)))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let api = api(&server, "/holidayapi/v1");
+    let countries = api.countries().get().await.unwrap();
+    assert_eq!(countries[1].subdivisions[0].code, "US-CA");
+    let holidays = api.holidays("US", 2020).get().await.unwrap();
+    assert_eq!(holidays.len(), 4);
+    let languages = api.languages().get().await.unwrap();
+    assert_eq!(languages[1].code, "ja");
+    let (date, weekday) = api.workday("US", "2020-07-01", 4).get().await.unwrap();
+    assert_eq!(
+        (date.as_str(), weekday.iso_weekday()),
+        ("2020-07-07", Some(2))
+    );
+    let workdays = api
+        .workdays("US", "2020-07-01", "2020-07-31")
+        .get()
+        .await
+        .unwrap();
+    assert_eq!(workdays, 21);
+}
+
+#[tokio::test]
+async fn test_expired_key() {
+    let server = MockServer::start().await;
+    Mock::given(path("/v1/countries"))
+        .respond_with(ResponseTemplate::new(401).set_body_raw(
+            r#"{"status": 401, "error": "Invalid key."}"#,
+            "application/json",
+        ))
+        .mount(&server)
+        .await;
+
+    let api = api(&server, "/v1/");
+    let err = api.countries().get().await.unwrap_err();
+    assert!(
+        matches!(&err, HolidayAPIError::InvalidOrExpiredKey(message) if message == "Invalid key."),
+        "Unexpected error: {}",
+        err
+    );
+    assert_eq!(api.requests_made(), 1);
+}
+
+#[tokio::test]
+async fn test_unexpected_content_type() {
+    let server = MockServer::start().await;
+    Mock::given(path("/v1/holidays"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_raw("<html>Please log in</html>", "text/html; charset=utf-8"),
+        )
+        .mount(&server)
+        .await;
+
+    let request = api(&server, "/v1/").holidays("US", 2020);
+    for result in [
+        request.clone().get_raw().await,
+        request.clone().format(Format::Csv).get_raw().await,
+    ] {
+        assert!(
+            matches!(&result, Err(HolidayAPIError::UnexpectedContentType(content_type)) if content_type == "text/html; charset=utf-8"),
+            "Unexpected result: {:?}",
+            result
+        );
+    }
+    assert!(matches!(
+        request.stream().await.err(),
+        Some(HolidayAPIError::UnexpectedContentType(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_invalid_body() {
+    let server = MockServer::start().await;
+    Mock::given(path("/v1/languages"))
+        .respond_with(json(r#"{"status": 200, "languages": "#.into()))
+        .mount(&server)
+        .await;
+
+    let err = api(&server, "/v1/").languages().get().await.unwrap_err();
+    assert!(
+        matches!(&err, HolidayAPIError::Parse { body_snippet, .. } if body_snippet.contains("languages")),
+        "Unexpected error: {}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn test_cache() {
+    let server = MockServer::start().await;
+    Mock::given(path("/v1/holidays"))
+        .respond_with(json(fixture("holidays")))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let cache = Arc::new(MemoryCache::new());
+    let api = HolidayAPI::builder(KEY)
+        .base_url(Url::parse(&format!("{}/v1/", server.uri())).unwrap())
+        .cache(cache.clone())
+        .build()
+        .unwrap();
+
+    let first = api
+        .holidays("US", 2020)
+        .public(true)
+        .month(7)
+        .get()
+        .await
+        .unwrap();
+    let again = api
+        .holidays("US", 2020)
+        .month(7)
+        .public(true)
+        .get()
+        .await
+        .unwrap();
+    assert_eq!(first.len(), again.len());
+    assert_eq!(cache.len(), 1);
+
+    assert_eq!(api.requests_made(), 1);
+
+    api.clone().holidays("US", 2021).get().await.unwrap();
+    assert_eq!(cache.len(), 2);
+    assert_eq!(api.requests_made(), 2);
+}
+
+#[tokio::test]
+async fn test_redirect_policy() {
+    let server = MockServer::start().await;
+    let other = MockServer::start().await;
+    let redirect = |to: String| ResponseTemplate::new(302).insert_header("Location", to);
+    Mock::given(path("/v1/languages"))
+        .respond_with(redirect(format!("{}/v2/languages", server.uri())))
+        .mount(&server)
+        .await;
+    Mock::given(path("/v1/countries"))
+        .respond_with(redirect(format!("{}/v1/countries", other.uri())))
+        .mount(&server)
+        .await;
+    Mock::given(path("/v2/languages"))
+        .respond_with(json(fixture("languages")))
+        .mount(&server)
+        .await;
+    Mock::given(path("/v1/countries"))
+        .respond_with(json(fixture("countries")))
+        .mount(&other)
+        .await;
+
+    let api = |policy: RedirectPolicy| {
+        HolidayAPI::builder(KEY)
+            .base_url(Url::parse(&format!("{}/v1/", server.uri())).unwrap())
+            .redirect(policy)
+            .build()
+            .unwrap()
+    };
+
+    let same_origin = api(RedirectPolicy::SameOrigin);
+    assert!(same_origin.languages().get().await.is_ok());
+    let err = same_origin.countries().get().await.unwrap_err();
+    assert!(matches!(err, HolidayAPIError::RequestError(ref e, _) if e.is_redirect()));
+    assert_eq!(other.received_requests().await.unwrap().len(), 0);
+
+    let none = api(RedirectPolicy::None);
+    assert!(none.languages().get().await.is_err());
+
+    let any = api(RedirectPolicy::Any);
+    assert!(any.countries().get().await.is_ok());
+    assert_eq!(other.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_redirect_limit() {
+    let mut hops = Vec::new();
+    for policy in [RedirectPolicy::SameOrigin, RedirectPolicy::Any] {
+        let server = MockServer::start().await;
+        let location = format!("{}/v1/languages", server.uri());
+        Mock::given(path("/v1/languages"))
+            .respond_with(ResponseTemplate::new(302).insert_header("Location", location))
+            .mount(&server)
+            .await;
+        let api = HolidayAPI::builder(KEY)
+            .base_url(Url::parse(&format!("{}/v1/", server.uri())).unwrap())
+            .redirect(policy)
+            .build()
+            .unwrap();
+        let err = api.languages().get().await.unwrap_err();
+        assert!(matches!(err, HolidayAPIError::RequestError(ref e, _) if e.is_redirect()));
+        hops.push(server.received_requests().await.unwrap().len());
+    }
+    assert_eq!(hops, [10, 10]);
+}
+
+#[tokio::test]
+async fn test_retry() {
+    let server = MockServer::start().await;
+    Mock::given(path("/v1/workdays"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .mount(&server)
+        .await;
+    Mock::given(path("/v1/workdays"))
+        .respond_with(json(fixture("workdays")))
+        .mount(&server)
+        .await;
+    Mock::given(path("/v1/languages"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let api = |mut retry: RetryPolicy| {
+        HolidayAPI::builder(KEY)
+            .base_url(Url::parse(&format!("{}/v1/", server.uri())).unwrap())
+            .retry(retry.base_delay(Duration::from_millis(1)))
+            .build()
+            .unwrap()
+    };
+    let workdays = api(RetryPolicy::new(2)).workdays("US", "2020-07-01", "2020-07-31");
+    assert_eq!(workdays.get().await.unwrap(), 21);
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+
+    let never = api(RetryPolicy::new(5).should_retry(|_| false));
+    assert!(never.languages().get().await.is_err());
+    let twice = api(RetryPolicy::new(2));
+    assert!(twice.languages().get().await.is_err());
+    assert_eq!(server.received_requests().await.unwrap().len(), 3 + 1 + 3);
+}
+
+#[tokio::test]
+async fn test_accept_language() {
+    let server = MockServer::start().await;
+    Mock::given(path("/v1/languages"))
+        .and(header("Accept-Language", "ja"))
+        .respond_with(json(fixture("languages")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(path("/v1/countries"))
+        .and(header_exists("Accept-Language"))
+        .respond_with(ResponseTemplate::new(400))
+        .mount(&server)
+        .await;
+    Mock::given(path("/v1/countries"))
+        .respond_with(json(fixture("countries")))
+        .mount(&server)
+        .await;
+
+    let base_url = Url::parse(&format!("{}/v1/", server.uri())).unwrap();
+    let api = HolidayAPI::builder(KEY)
+        .base_url(base_url.clone())
+        .accept_language("ja")
+        .build()
+        .unwrap();
+    assert!(api.languages().get().await.is_ok());
+    assert!(HolidayAPI::with_base_url(KEY, base_url)
+        .unwrap()
+        .countries()
+        .get()
+        .await
+        .is_ok());
+
+    let invalid = HolidayAPI::builder(KEY).accept_language("en\n").build();
+    assert!(matches!(invalid, Err(HolidayAPIError::InvalidRequest(_))));
+}
+
+/// Serves `body` to every request over keep-alive connections, counting the
+/// connections accepted.
+async fn keep_alive_server(body: String) -> (Url, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = Url::parse(&format!("http://{}/v1/", listener.local_addr().unwrap())).unwrap();
+    let connections = Arc::new(AtomicUsize::new(0));
+    let accepted = connections.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            accepted.fetch_add(1, Ordering::SeqCst);
+            let body = body.clone();
+            tokio::spawn(async move {
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                while let Ok(read @ 1..) = socket.read(&mut buf).await {
+                    request.extend_from_slice(&buf[..read]);
+                    if !request.windows(4).any(|end| end == b"\r\n\r\n") {
+                        continue;
+                    }
+                    request.clear();
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    if socket.write_all(response.as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    (url, connections)
+}
+
+#[tokio::test]
+async fn test_clones_share_connections() {
+    let (url, connections) = keep_alive_server(fixture("languages")).await;
+    let api = HolidayAPI::with_base_url(KEY, url.clone()).unwrap();
+
+    api.languages().get().await.unwrap();
+    let clone = api.clone();
+    clone.languages().get().await.unwrap();
+    clone.clone().languages().get().await.unwrap();
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+
+    let other = HolidayAPI::with_base_url(KEY, url).unwrap();
+    other.languages().get().await.unwrap();
+    assert_eq!(connections.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_pagination() {
+    let server = MockServer::start().await;
+    let mut first: serde_json::Value = serde_json::from_str(&fixture("holidays")).unwrap();
+    let mut second = first.clone();
+    first["holidays"].as_array_mut().unwrap().truncate(1);
+    first["pagination"] = serde_json::json!({ "next_page": 2 });
+    second["holidays"].as_array_mut().unwrap().remove(0);
+    Mock::given(path("/v1/holidays"))
+        .and(query_param("page", "2"))
+        .respond_with(json(second.to_string()))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(path("/v1/holidays"))
+        .respond_with(json(first.to_string()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let holidays = api(&server, "/v1/")
+        .holidays("US", 2020)
+        .get()
+        .await
+        .unwrap();
+    assert_eq!(holidays.len(), 4);
+    assert_eq!(holidays[0].name, "New Year's Day");
+    assert_eq!(holidays[3].name, "Christmas Day");
+}
+
+#[tokio::test]
+async fn test_pagination_limit() {
+    let server = MockServer::start().await;
+    let mut body: serde_json::Value = serde_json::from_str(&fixture("holidays")).unwrap();
+    body["pagination"] = serde_json::json!({ "next_cursor": "again" });
+    Mock::given(path("/v1/holidays"))
+        .respond_with(json(body.to_string()))
+        .expect(Request::<HolidaysResponse>::MAX_PAGES as u64)
+        .mount(&server)
+        .await;
+
+    let err = api(&server, "/v1/")
+        .holidays("US", 2020)
+        .get()
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, HolidayAPIError::InvalidResponse(_)),
+        "Unexpected error: {}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn test_request_header() {
+    let server = MockServer::start().await;
+    Mock::given(path("/v1/languages"))
+        .and(header("Idempotency-Key", "second"))
+        .respond_with(json(fixture("languages")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = api(&server, "/v1/");
+    let languages = api
+        .languages()
+        .header("idempotency-key", "first")
+        .header("Idempotency-Key", "second")
+        .get()
+        .await;
+    assert!(languages.is_ok());
+
+    let invalid = api
+        .languages()
+        .header("Idempotency Key", "first")
+        .get()
+        .await;
+    assert!(matches!(
+        invalid,
+        Err(HolidayAPIError::Validation(ValidationError::InvalidHeader(
+            _
+        )))
+    ));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_new_unchecked() {
+    let server = MockServer::start().await;
+    Mock::given(path("/v1/languages"))
+        .and(query_param("key", "test"))
+        .respond_with(json(fixture("languages")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let base_url = Url::parse(&format!("{}/v1", server.uri())).unwrap();
+    let api = HolidayAPI::new_unchecked("test", base_url);
+    assert_eq!(api.languages().get().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_response_headers() {
+    let server = MockServer::start().await;
+    Mock::given(path("/v1/languages"))
+        .respond_with(
+            json(fixture("languages"))
+                .insert_header("ETag", "\"abc\"")
+                .insert_header("X-RateLimit-Remaining", "9998"),
+        )
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let api = HolidayAPI::builder(KEY)
+        .base_url(Url::parse(&format!("{}/v1/", server.uri())).unwrap())
+        .cache(MemoryCache::new())
+        .build()
+        .unwrap();
+    for _ in 0..2 {
+        let (headers, body) = api.languages().get_raw_with_headers().await.unwrap();
+        assert_eq!(headers["etag"], "\"abc\"");
+        assert_eq!(headers["x-ratelimit-remaining"], "9998");
+        assert!(body.contains("Japanese"));
+    }
+
+    // Bypasses the cache and leaves the body unread.
+    let response = api.languages().get_response().await.unwrap();
+    assert_eq!(response.headers()["etag"], "\"abc\"");
+    assert!(response.text().await.unwrap().contains("Japanese"));
+}
+
+#[tokio::test]
+async fn test_empty_body() {
+    let server = MockServer::start().await;
+    Mock::given(path("/v1/holidays"))
+        .respond_with(json("".into()))
+        .mount(&server)
+        .await;
+
+    let request = api(&server, "/v1/").holidays("US", 2020);
+    assert_eq!(request.clone().get_raw().await.unwrap(), "");
+    let err = request.clone().get().await.unwrap_err();
+    assert!(
+        matches!(err, HolidayAPIError::EmptyResponse),
+        "Unexpected error: {}",
+        err
+    );
+
+    let holidays: Vec<_> = request.stream().await.unwrap().collect().await;
+    assert!(matches!(
+        holidays[..],
+        [Err(HolidayAPIError::EmptyResponse)]
+    ));
+}
+
+#[tokio::test]
+async fn test_stream_empty_holidays() {
+    for holidays in [r#", "holidays": []"#, r#", "holidays": null"#, ""] {
+        let server = MockServer::start().await;
+        let body = format!(
+            r#"{{"status": 200, "requests": {{"used": 1, "available": 9999, "resets": "2019-10-01 00:00:00"}}{}}}"#,
+            holidays
+        );
+        Mock::given(path("/v1/holidays"))
+            .respond_with(json(body.clone()))
+            .mount(&server)
+            .await;
+
+        let request = api(&server, "/v1/").holidays("US", 2020);
+        let holidays: Vec<_> = request.stream().await.unwrap().collect().await;
+        assert!(holidays.is_empty(), "Should be empty for {}", body);
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[tokio::test]
+async fn test_workdays_with_weekend_observed_in_previous_year() {
+    use chrono::{NaiveDate, Weekday};
+
+    let server = MockServer::start().await;
+    let mut next_year: serde_json::Value = serde_json::from_str(&fixture("holidays")).unwrap();
+    let holidays = next_year["holidays"].as_array_mut().unwrap();
+    holidays.truncate(1);
+    holidays[0]["date"] = "2022-01-01".into();
+    holidays[0]["observed"] = "2021-12-31".into();
+    let mut this_year = next_year.clone();
+    this_year["holidays"] = serde_json::json!([]);
+    Mock::given(path("/v1/holidays"))
+        .and(query_param("year", "2022"))
+        .respond_with(json(next_year.to_string()))
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(path("/v1/holidays"))
+        .respond_with(json(this_year.to_string()))
+        .mount(&server)
+        .await;
+
+    // New Year's Day 2022 is on a Saturday, observed on Friday 2021-12-31.
+    let api = api(&server, "/v1/");
+    let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+    let sat_sun = [Weekday::Sat, Weekday::Sun];
+    let workdays =
+        api.workdays_with_weekend("US", date(2021, 12, 30), date(2021, 12, 31), &sat_sun);
+    assert_eq!(workdays.await.unwrap(), 1);
+    let workday = api.workday_with_weekend("US", date(2021, 12, 30), 1, &sat_sun);
+    assert_eq!(workday.await.unwrap(), date(2022, 1, 3));
+}
+
+#[tokio::test]
+async fn test_skipped_holidays_observed_in_previous_year() {
+    let server = MockServer::start().await;
+    let mut next_year: serde_json::Value = serde_json::from_str(&fixture("holidays")).unwrap();
+    let holidays = next_year["holidays"].as_array_mut().unwrap();
+    holidays.truncate(1);
+    holidays[0]["date"] = "2022-01-01".into();
+    holidays[0]["observed"] = "2021-12-31".into();
+    holidays[0]["weekday"]["observed"] = serde_json::json!({ "name": "Friday", "numeric": "5" });
+    let mut this_year = next_year.clone();
+    this_year["holidays"] = serde_json::json!([]);
+    Mock::given(path("/v1/holidays"))
+        .and(query_param("year", "2022"))
+        .respond_with(json(next_year.to_string()))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(path("/v1/holidays"))
+        .respond_with(json(this_year.to_string()))
+        .mount(&server)
+        .await;
+
+    let skipped = api(&server, "/v1/")
+        .workdays("US", "2021-12-01", "2021-12-31")
+        .skipped_holidays()
+        .await
+        .unwrap();
+    let observed: Vec<_> = skipped.iter().map(|h| h.observed.as_str()).collect();
+    assert_eq!(observed, ["2021-12-31"]);
+}
+
+#[tokio::test]
+async fn test_max_response_bytes_error_body() {
+    let server = MockServer::start().await;
+    Mock::given(path("/v1/languages"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("x".repeat(1024)))
+        .mount(&server)
+        .await;
+
+    let api = api(&server, "/v1/").with_max_response_bytes(64);
+    assert!(matches!(
+        api.languages().get().await,
+        Err(HolidayAPIError::ResponseTooLarge(64))
+    ));
+}
+
+#[tokio::test]
+async fn test_strict_warnings() {
+    let server = MockServer::start().await;
+    let mut body: serde_json::Value = serde_json::from_str(&fixture("languages")).unwrap();
+    body["warning"] = "The `search` parameter is deprecated.".into();
+    Mock::given(path("/v1/languages"))
+        .respond_with(json(body.to_string()))
+        .mount(&server)
+        .await;
+
+    let api = |strict| {
+        HolidayAPI::builder(KEY)
+            .base_url(Url::parse(&format!("{}/v1/", server.uri())).unwrap())
+            .strict_warnings(strict)
+            .build()
+            .unwrap()
+    };
+    assert_eq!(api(false).languages().get().await.unwrap().len(), 2);
+    let err = api(true).languages().get().await.unwrap_err();
+    assert!(
+        matches!(&err, HolidayAPIError::Warning(ApiWarning::Deprecation(d)) if d.parameter == "search"),
+        "Unexpected error: {}",
+        err
+    );
+    assert!(api(true).languages().get_raw().await.is_ok());
+}
+
+#[tokio::test]
+async fn test_rate_limit() {
+    let server = MockServer::start().await;
+    Mock::given(path("/v1/languages"))
+        .respond_with(json(fixture("languages")))
+        .expect(4)
+        .mount(&server)
+        .await;
+
+    let api = HolidayAPI::builder(KEY)
+        .base_url(Url::parse(&format!("{}/v1/", server.uri())).unwrap())
+        .rate_limit(10)
+        .build()
+        .unwrap();
+    let clone = api.clone();
+    let start = std::time::Instant::now();
+    let (first, second) = futures_util::future::join(
+        async { (api.languages().get().await, api.languages().get().await) },
+        async { (clone.languages().get().await, clone.languages().get().await) },
+    )
+    .await;
+    assert!(first.0.is_ok() && first.1.is_ok() && second.0.is_ok() && second.1.is_ok());
+    assert!(start.elapsed() >= Duration::from_millis(300));
+
+    assert!(matches!(
+        HolidayAPI::builder(KEY).rate_limit(0).build(),
+        Err(HolidayAPIError::InvalidRequest(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_multi_country_order() {
+    let server = MockServer::start().await;
+    let holidays = fixture("holidays");
+    Mock::given(path("/v1/holidays"))
+        .and(query_param("country", "US"))
+        .respond_with(json(holidays.clone()).set_delay(Duration::from_millis(200)))
+        .mount(&server)
+        .await;
+    Mock::given(path("/v1/holidays"))
+        .respond_with(json(holidays))
+        .mount(&server)
+        .await;
+
+    // US completes last, but still comes first.
+    let api = api(&server, "/v1/");
+    let countries = ["US", "JP", "FR"];
+    let on_date = api.holidays_on_date(&countries, 2020, 12, 25, 3).await;
+    let order: Vec<_> = on_date
+        .iter()
+        .map(|(country, _)| country.as_str())
+        .collect();
+    assert_eq!(order, countries);
+    let counts = api
+        .public_holiday_counts(&countries, 2020, 3)
+        .await
+        .unwrap();
+    let order: Vec<_> = counts.iter().map(|(country, _)| country.as_str()).collect();
+    assert_eq!(order, countries);
+}
+
+#[tokio::test]
+async fn test_subdivisions_without_any() {
+    let server = MockServer::start().await;
+    let mut body: serde_json::Value = serde_json::from_str(&fixture("holidays")).unwrap();
+    for holiday in body["holidays"].as_array_mut().unwrap() {
+        holiday["country"] = "MC".into();
+        holiday["subdivisions"] = serde_json::Value::Null;
+    }
+    Mock::given(path("/v1/holidays"))
+        .and(query_param("country", "MC"))
+        .and(query_param("subdivisions", "true"))
+        .respond_with(json(body.to_string()))
+        .mount(&server)
+        .await;
+    Mock::given(path("/v1/holidays"))
+        .and(query_param("country", "VA"))
+        .respond_with(ResponseTemplate::new(400).set_body_raw(
+            r#"{"status": 400, "error": "Subdivisions are not supported."}"#,
+            "application/json",
+        ))
+        .mount(&server)
+        .await;
+
+    let api = api(&server, "/v1/");
+    let holidays = api.holidays("MC", 2020).subdivisions().get().await.unwrap();
+    assert!(!holidays.is_empty());
+    assert!(holidays
+        .iter()
+        .all(|holiday| holiday.subdivisions.is_empty()));
+
+    let err = api
+        .holidays("VA", 2020)
+        .subdivisions()
+        .get()
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, HolidayAPIError::RequestError(_, message) if message
+            == "Subdivisions are not supported. (subdivisions were requested for VA, which may not have any)"),
+        "Unexpected error: {}",
+        err
+    );
+    let err = api.holidays("VA", 2020).get().await.unwrap_err();
+    assert!(
+        matches!(&err, HolidayAPIError::RequestError(_, message) if message
+            == "Subdivisions are not supported."),
+        "Unexpected error: {}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn test_stale_while_revalidate() {
+    let server = MockServer::start().await;
+    let updated = fixture("languages").replace("Japanese", "Nihongo");
+    Mock::given(path("/v1/languages"))
+        .respond_with(json(fixture("languages")))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(path("/v1/languages"))
+        .respond_with(json(updated).set_delay(Duration::from_millis(100)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = HolidayAPI::builder(KEY)
+        .base_url(Url::parse(&format!("{}/v1/", server.uri())).unwrap())
+        .cache(MemoryCache::new())
+        .cache_max_age(Duration::from_millis(50))
+        .cache_stale_while_revalidate(Duration::from_secs(60))
+        .build()
+        .unwrap();
+    let name = |languages: Vec<Language>| languages[1].name.clone();
+    assert_eq!(name(api.languages().get().await.unwrap()), "Japanese");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Stale: served at once, without waiting for the slow refresh.
+    let start = std::time::Instant::now();
+    for _ in 0..3 {
+        assert_eq!(name(api.languages().get().await.unwrap()), "Japanese");
+    }
+    assert!(start.elapsed() < Duration::from_millis(100));
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(name(api.languages().get().await.unwrap()), "Nihongo");
+}
+
+#[tokio::test]
+async fn test_get_by_uuid_duplicates() {
+    let server = MockServer::start().await;
+    let mut body: serde_json::Value = serde_json::from_str(&fixture("holidays")).unwrap();
+    let holidays = body["holidays"].as_array_mut().unwrap();
+    let mut regional = holidays[0].clone();
+    regional["subdivisions"] = serde_json::json!(["US-CA"]);
+    holidays.push(regional);
+    let duplicate = holidays[1].clone();
+    holidays.push(duplicate);
+    Mock::given(path("/v1/holidays"))
+        .respond_with(json(body.to_string()))
+        .mount(&server)
+        .await;
+
+    let holidays = api(&server, "/v1/")
+        .holidays("US", 2020)
+        .get_by_uuid()
+        .await
+        .unwrap();
+    assert_eq!(holidays.len(), 4);
+    let new_year = holidays
+        .values()
+        .find(|h| h.name == "New Year's Day")
+        .unwrap();
+    assert_eq!(new_year.subdivisions, ["US-CA"]);
+}